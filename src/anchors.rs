@@ -0,0 +1,32 @@
+use crate::{MobileconfTLSCert, MobileconfWifi};
+
+/// What a `PayloadCertificateAnchorUUID` entry points at.
+#[derive(Debug)]
+pub enum Anchor<'a> {
+    Resolved(&'a MobileconfTLSCert),
+    // no cert in the profile has this PayloadUUID
+    Missing(&'a str),
+}
+
+/// Resolve the anchor UUIDs of each wifi to the certs they refer to.
+pub fn link_anchors<'a>(
+    wifis: &'a [MobileconfWifi],
+    certs: &'a [MobileconfTLSCert],
+) -> Vec<(&'a MobileconfWifi, Vec<Anchor<'a>>)> {
+    wifis
+        .iter()
+        .map(|wifi| {
+            let anchors = wifi
+                .PayloadCertificateAnchorUUID
+                .iter()
+                .map(|uuid| {
+                    certs
+                        .iter()
+                        .find(|cert| cert.PayloadUUID == *uuid)
+                        .map_or(Anchor::Missing(uuid), Anchor::Resolved)
+                })
+                .collect();
+            (wifi, anchors)
+        })
+        .collect()
+}
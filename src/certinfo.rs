@@ -0,0 +1,85 @@
+use openssl::asn1::Asn1Time;
+use openssl::error::ErrorStack;
+use openssl::x509::X509NameRef;
+use openssl::x509::X509Ref;
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Human readable summary of an X509 certificate.
+#[derive(Debug, Serialize)]
+pub struct CertInfo {
+    pub subject: String,
+    pub issuer: String,
+    pub serial: String,
+    pub not_before: String,
+    pub not_after: String,
+}
+
+impl CertInfo {
+    pub fn from_x509(cert: &X509Ref) -> Result<Self, ErrorStack> {
+        Result::Ok(CertInfo {
+            subject: format_name(cert.subject_name()),
+            issuer: format_name(cert.issuer_name()),
+            serial: cert.serial_number().to_bn()?.to_hex_str()?.to_string(),
+            not_before: cert.not_before().to_string(),
+            not_after: cert.not_after().to_string(),
+        })
+    }
+}
+
+/// Where the current time falls relative to a certificate's validity period.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Validity {
+    Valid,
+    Expired,
+    NotYetValid,
+}
+
+impl Validity {
+    pub fn of(cert: &X509Ref) -> Result<Self, ErrorStack> {
+        let now = Asn1Time::days_from_now(0)?;
+
+        if cert.not_after().compare(&now)? == Ordering::Less {
+            Result::Ok(Validity::Expired)
+        } else if cert.not_before().compare(&now)? == Ordering::Greater {
+            Result::Ok(Validity::NotYetValid)
+        } else {
+            Result::Ok(Validity::Valid)
+        }
+    }
+}
+
+/// Whole days from now until the certificate's notAfter, negative once it has
+/// expired.
+pub fn days_remaining(cert: &X509Ref) -> Result<i32, ErrorStack> {
+    let now = Asn1Time::days_from_now(0)?;
+    Result::Ok(now.diff(cert.not_after())?.days)
+}
+
+impl fmt::Display for Validity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Validity::Valid => "valid",
+            Validity::Expired => "expired",
+            Validity::NotYetValid => "not yet valid",
+        })
+    }
+}
+
+/// Format a name as `CN=example, O=Org` using the short names of the entries.
+pub(crate) fn format_name(name: &X509NameRef) -> String {
+    name.entries()
+        .map(|entry| {
+            let key = entry.object().nid().short_name().unwrap_or("?");
+            let value = entry
+                .data()
+                .as_utf8()
+                .map(|value| value.to_string())
+                .unwrap_or_default();
+            format!("{}={}", key, value)
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
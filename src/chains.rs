@@ -0,0 +1,79 @@
+use crate::certinfo::format_name;
+use crate::MobileconfTLSCert;
+use openssl::x509::{X509Ref, X509VerifyResult, X509};
+
+/// How a chain built by `build_chains` ends.
+#[derive(Debug, PartialEq)]
+pub enum ChainEnd {
+    // the last cert is a self-signed root
+    Root,
+    // no cert in the profile issued the last one, holds its issuer name
+    MissingIssuer(String),
+    // the last cert was issued by one already in the chain
+    Loop,
+}
+
+/// Certs from a profile ordered from the leaf up to its root.
+#[derive(Debug)]
+pub struct CertChain<'a> {
+    pub certs: Vec<&'a MobileconfTLSCert>,
+    pub end: ChainEnd,
+}
+
+/// Whether `issuer` issued `cert`: the names match and the signature checks
+/// out with the issuer's key.
+fn issued_by(cert: &X509Ref, issuer: &X509Ref) -> bool {
+    issuer.issued(cert) == X509VerifyResult::OK
+        && issuer
+            .public_key()
+            .and_then(|key| cert.verify(&key))
+            .unwrap_or(false)
+}
+
+/// Build the chain of every leaf cert in the profile, a leaf being a cert
+/// that didn't issue any of the others. Certs that don't decode are left
+/// out, parsing reports them already.
+pub fn build_chains(certs: &[MobileconfTLSCert]) -> Vec<CertChain> {
+    let decoded: Vec<(&MobileconfTLSCert, X509)> = certs
+        .iter()
+        .filter_map(|cert| cert.to_x509().ok().map(|x509| (cert, x509)))
+        .collect();
+
+    let issuer_of = |index: usize| {
+        decoded
+            .iter()
+            .position(|(_, issuer)| issued_by(&decoded[index].1, issuer))
+    };
+
+    let is_leaf = |index: usize| {
+        !decoded
+            .iter()
+            .enumerate()
+            .any(|(other, _)| other != index && issuer_of(other) == Some(index))
+    };
+
+    (0..decoded.len())
+        .filter(|&index| is_leaf(index))
+        .map(|leaf| {
+            let mut chain = vec![leaf];
+            let end = loop {
+                let current = chain[chain.len() - 1];
+                match issuer_of(current) {
+                    Some(issuer) if issuer == current => break ChainEnd::Root,
+                    Some(issuer) if chain.contains(&issuer) => break ChainEnd::Loop,
+                    Some(issuer) => chain.push(issuer),
+                    None => {
+                        break ChainEnd::MissingIssuer(format_name(
+                            decoded[current].1.issuer_name(),
+                        ))
+                    }
+                }
+            };
+
+            CertChain {
+                certs: chain.into_iter().map(|index| decoded[index].0).collect(),
+                end,
+            }
+        })
+        .collect()
+}
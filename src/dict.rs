@@ -0,0 +1,123 @@
+use crate::MobileconfError;
+use plist::Dictionary;
+use plist::Value;
+
+pub(crate) fn get_value<'a>(dict: &'a Dictionary, key: &str) -> Result<&'a Value, MobileconfError> {
+    dict.get(key)
+        .ok_or_else(|| MobileconfError::MissingKey(key.to_string()))
+}
+
+pub(crate) fn get_string(dict: &Dictionary, key: &str) -> Result<String, MobileconfError> {
+    get_value(dict, key)?
+        .as_string()
+        .ok_or_else(|| MobileconfError::wrong_type(key, "string"))
+        .map(str::to_string)
+}
+
+// the _opt variants return None for a missing key, but a present key of the
+// wrong type is still an error
+
+pub(crate) fn get_string_opt(
+    dict: &Dictionary,
+    key: &str,
+) -> Result<Option<String>, MobileconfError> {
+    dict.get(key)
+        .map(|v| {
+            v.as_string()
+                .ok_or_else(|| MobileconfError::wrong_type(key, "string"))
+                .map(str::to_string)
+        })
+        .transpose()
+}
+
+pub(crate) fn get_bool_opt(dict: &Dictionary, key: &str) -> Result<Option<bool>, MobileconfError> {
+    dict.get(key)
+        .map(|v| {
+            v.as_boolean()
+                .ok_or_else(|| MobileconfError::wrong_type(key, "boolean"))
+        })
+        .transpose()
+}
+
+pub(crate) fn get_int_opt(dict: &Dictionary, key: &str) -> Result<Option<i64>, MobileconfError> {
+    dict.get(key)
+        .map(|v| {
+            v.as_signed_integer()
+                .ok_or_else(|| MobileconfError::wrong_type(key, "integer"))
+        })
+        .transpose()
+}
+
+pub(crate) fn get_data_opt<'a>(
+    dict: &'a Dictionary,
+    key: &str,
+) -> Result<Option<&'a [u8]>, MobileconfError> {
+    dict.get(key)
+        .map(|v| {
+            v.as_data()
+                .ok_or_else(|| MobileconfError::wrong_type(key, "data"))
+        })
+        .transpose()
+}
+
+pub(crate) fn get_array_opt<'a>(
+    dict: &'a Dictionary,
+    key: &str,
+) -> Result<Option<&'a Vec<Value>>, MobileconfError> {
+    dict.get(key)
+        .map(|v| {
+            v.as_array()
+                .ok_or_else(|| MobileconfError::wrong_type(key, "array"))
+        })
+        .transpose()
+}
+
+pub(crate) fn get_dict_opt<'a>(
+    dict: &'a Dictionary,
+    key: &str,
+) -> Result<Option<&'a Dictionary>, MobileconfError> {
+    dict.get(key)
+        .map(|v| {
+            v.as_dictionary()
+                .ok_or_else(|| MobileconfError::wrong_type(key, "dictionary"))
+        })
+        .transpose()
+}
+
+/// The string entries of an array, other entries are skipped.
+pub(crate) fn get_strings_opt(
+    dict: &Dictionary,
+    key: &str,
+) -> Result<Option<Vec<String>>, MobileconfError> {
+    Result::Ok(get_array_opt(dict, key)?.map(|values| {
+        values
+            .iter()
+            .filter_map(Value::as_string)
+            .map(str::to_string)
+            .collect()
+    }))
+}
+
+pub(crate) fn get_dict<'a>(
+    dict: &'a Dictionary,
+    key: &str,
+) -> Result<&'a Dictionary, MobileconfError> {
+    get_value(dict, key)?
+        .as_dictionary()
+        .ok_or_else(|| MobileconfError::wrong_type(key, "dictionary"))
+}
+
+pub(crate) fn get_array<'a>(
+    dict: &'a Dictionary,
+    key: &str,
+) -> Result<&'a Vec<Value>, MobileconfError> {
+    get_value(dict, key)?
+        .as_array()
+        .ok_or_else(|| MobileconfError::wrong_type(key, "array"))
+}
+
+/// Payloads are the dictionaries inside the top-level `PayloadContent` array.
+pub(crate) fn as_payload(v: &Value) -> Result<&Dictionary, MobileconfError> {
+    v.as_dictionary()
+        .ok_or_else(|| MobileconfError::wrong_type("PayloadContent", "dictionary"))
+}
@@ -0,0 +1,356 @@
+use crate::dict::get_string_opt;
+use crate::profile::{limited_payload_contents, load_plist};
+use crate::MobileconfError;
+use crate::MobileconfWifi;
+use crate::ParseOptions;
+use crate::Secret;
+use plist::{Dictionary, Value};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// A payload as identified across two revisions of a profile.
+#[allow(non_snake_case)]
+#[derive(Debug, Clone, Serialize)]
+pub struct PayloadKey {
+    pub PayloadIdentifier: String,
+    pub PayloadUUID: String,
+    pub PayloadType: String,
+}
+
+/// One field that differs between the two revisions of a payload.
+#[derive(Debug, Serialize)]
+pub struct FieldChange {
+    pub field: String,
+    // None when the field is only in the other revision
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ChangedPayload {
+    pub payload: PayloadKey,
+    pub changes: Vec<FieldChange>,
+}
+
+/// What changed from one profile to the next, see [`diff_profiles`].
+#[derive(Debug, Default, Serialize)]
+pub struct ProfileDiff {
+    pub added: Vec<PayloadKey>,
+    pub removed: Vec<PayloadKey>,
+    pub changed: Vec<ChangedPayload>,
+}
+
+impl ProfileDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+fn payload_key(payload: &Value) -> PayloadKey {
+    let field = |key| {
+        payload
+            .as_dictionary()
+            .and_then(|dict| get_string_opt(dict, key).ok().flatten())
+            .unwrap_or_default()
+    };
+
+    PayloadKey {
+        PayloadIdentifier: field("PayloadIdentifier"),
+        PayloadUUID: field("PayloadUUID"),
+        PayloadType: field("PayloadType"),
+    }
+}
+
+/// Keys that hold passwords and shared secrets in the payloads we know of.
+fn is_secret_key(key: &str) -> bool {
+    key.contains("Password") || key.contains("Secret") || key == "PSK"
+}
+
+/// As json, so the field diffs read like the raw ones. `None` for null.
+fn show<T: Serialize>(value: &T) -> Option<String> {
+    match serde_json::to_value(value) {
+        Result::Ok(serde_json::Value::Null) => None,
+        Result::Ok(value) => Some(value.to_string()),
+        Result::Err(_) => None,
+    }
+}
+
+fn push_change<T: PartialEq + Serialize>(
+    changes: &mut Vec<FieldChange>,
+    field: &str,
+    old: &T,
+    new: &T,
+) {
+    if old != new {
+        changes.push(FieldChange {
+            field: field.to_string(),
+            old: show(old),
+            new: show(new),
+        });
+    }
+}
+
+/// The fields of a wifi payload an administrator cares about, by name.
+fn wifi_changes(old: &MobileconfWifi, new: &MobileconfWifi) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+
+    push_change(&mut changes, "SSID", &old.SSID, &new.SSID);
+    push_change(&mut changes, "UserName", &old.UserName, &new.UserName);
+    push_change(
+        &mut changes,
+        "OuterIdentity",
+        &old.OuterIdentity,
+        &new.OuterIdentity,
+    );
+    // Secret compares the real value but serializes redacted
+    push_change(
+        &mut changes,
+        "UserPassword",
+        &old.UserPassword,
+        &new.UserPassword,
+    );
+    push_change(&mut changes, "EAPType", &old.EAPType, &new.EAPType);
+    push_change(
+        &mut changes,
+        "TTLSInnerAuthentication",
+        &old.TTLSInnerAuthentication,
+        &new.TTLSInnerAuthentication,
+    );
+    push_change(
+        &mut changes,
+        "TLSTrustedServerNames",
+        &old.TLSTrustedServerNames,
+        &new.TLSTrustedServerNames,
+    );
+    push_change(
+        &mut changes,
+        "PayloadCertificateAnchorUUID",
+        &old.PayloadCertificateAnchorUUID,
+        &new.PayloadCertificateAnchorUUID,
+    );
+    push_change(&mut changes, "IsHotspot", &old.IsHotspot, &new.IsHotspot);
+    push_change(
+        &mut changes,
+        "DisplayedOperatorName",
+        &old.DisplayedOperatorName,
+        &new.DisplayedOperatorName,
+    );
+    push_change(&mut changes, "DomainName", &old.DomainName, &new.DomainName);
+    push_change(
+        &mut changes,
+        "RoamingConsortiumOIs",
+        &old.RoamingConsortiumOIs,
+        &new.RoamingConsortiumOIs,
+    );
+    push_change(&mut changes, "proxy", &old.proxy, &new.proxy);
+
+    changes
+}
+
+fn show_value(key: &str, value: &Value) -> Result<String, MobileconfError> {
+    let shown = serde_json::to_string(value)?;
+    if is_secret_key(key) {
+        Result::Ok(Secret::from(shown).to_string())
+    } else {
+        Result::Ok(shown)
+    }
+}
+
+/// Compare the keys of two dictionaries. Keys in `nested` that hold a
+/// dictionary on both sides are compared key by key too, as `key.subkey`.
+fn key_changes(
+    prefix: &str,
+    old: &Dictionary,
+    new: &Dictionary,
+    nested: &[&str],
+    changes: &mut Vec<FieldChange>,
+) -> Result<(), MobileconfError> {
+    let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    for key in keys {
+        let (old, new) = (old.get(key), new.get(key));
+        if old == new {
+            continue;
+        }
+
+        let field = format!("{}{}", prefix, key);
+        if let (true, Some(Value::Dictionary(old)), Some(Value::Dictionary(new))) =
+            (nested.contains(&key.as_str()), old, new)
+        {
+            key_changes(&format!("{}.", field), old, new, &[], changes)?;
+            continue;
+        }
+
+        changes.push(FieldChange {
+            old: old.map(|value| show_value(&field, value)).transpose()?,
+            new: new.map(|value| show_value(&field, value)).transpose()?,
+            field,
+        });
+    }
+
+    Result::Ok(())
+}
+
+/// Compare the top level keys of two payloads we have no field list for.
+fn raw_changes(old: &Value, new: &Value) -> Result<Vec<FieldChange>, MobileconfError> {
+    let empty = Dictionary::new();
+    let old = old.as_dictionary().unwrap_or(&empty);
+    let new = new.as_dictionary().unwrap_or(&empty);
+
+    let mut changes = Vec::new();
+    key_changes("", old, new, &[], &mut changes)?;
+    Result::Ok(changes)
+}
+
+/// The `wifi_changes` field a raw key is copied into as is, so a change to
+/// the key always shows up as a change to the field.
+fn wifi_field(key: &str) -> Option<&'static str> {
+    match key {
+        "SSID_STR" => Some("SSID"),
+        "EAPClientConfiguration.UserName" => Some("UserName"),
+        "EAPClientConfiguration.OuterIdentity" => Some("OuterIdentity"),
+        "EAPClientConfiguration.UserPassword" => Some("UserPassword"),
+        "EAPClientConfiguration.TTLSInnerAuthentication" => Some("TTLSInnerAuthentication"),
+        "EAPClientConfiguration.TLSTrustedServerNames" => Some("TLSTrustedServerNames"),
+        "EAPClientConfiguration.PayloadCertificateAnchorUUID" => {
+            Some("PayloadCertificateAnchorUUID")
+        }
+        "IsHotspot" => Some("IsHotspot"),
+        "DisplayedOperatorName" => Some("DisplayedOperatorName"),
+        "DomainName" => Some("DomainName"),
+        "RoamingConsortiumOIs" => Some("RoamingConsortiumOIs"),
+        _ => None,
+    }
+}
+
+/// The raw key changes of a wifi payload that `wifi_changes` doesn't already
+/// report, so keys outside its fields aren't lost.
+fn wifi_raw_changes(
+    old: &Value,
+    new: &Value,
+    reported: &[FieldChange],
+) -> Result<Vec<FieldChange>, MobileconfError> {
+    let empty = Dictionary::new();
+    let old = old.as_dictionary().unwrap_or(&empty);
+    let new = new.as_dictionary().unwrap_or(&empty);
+
+    let mut changes = Vec::new();
+    key_changes("", old, new, &["EAPClientConfiguration"], &mut changes)?;
+    changes.retain(|change| match wifi_field(&change.field) {
+        Some(field) => !reported.iter().any(|reported| reported.field == field),
+        None => true,
+    });
+    Result::Ok(changes)
+}
+
+fn payloads_by_key<'a>(
+    plist: &'a Value,
+    options: &ParseOptions,
+) -> Result<BTreeMap<(String, String), (PayloadKey, &'a Value)>, MobileconfError> {
+    Result::Ok(
+        limited_payload_contents(plist, options)?
+            .iter()
+            .map(|payload| {
+                let key = payload_key(payload);
+                (
+                    (key.PayloadIdentifier.clone(), key.PayloadUUID.clone()),
+                    (key, payload),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Compare the payloads of two profiles, matching them up by
+/// `PayloadIdentifier` and `PayloadUUID`. Wifi payloads are compared field by
+/// field, plus whatever other keys changed, other payloads by their top level
+/// keys. Secrets are compared on their real value but redacted in the result
+/// unless `set_show_secrets` is on.
+pub fn diff_profiles(
+    old: &[u8],
+    new: &[u8],
+    options: &ParseOptions,
+) -> Result<ProfileDiff, MobileconfError> {
+    let (old, _) = load_plist(old, &options.verify)?;
+    let (new, _) = load_plist(new, &options.verify)?;
+    let old = payloads_by_key(&old, options)?;
+    let new = payloads_by_key(&new, options)?;
+
+    let mut diff = ProfileDiff::default();
+
+    for (id, (key, old_payload)) in &old {
+        let new_payload = match new.get(id) {
+            Some((_, payload)) => payload,
+            None => {
+                diff.removed.push(key.clone());
+                continue;
+            }
+        };
+        if old_payload == new_payload {
+            continue;
+        }
+
+        let changes = match (
+            MobileconfWifi::parse(old_payload),
+            MobileconfWifi::parse(new_payload),
+        ) {
+            (Result::Ok(old_wifi), Result::Ok(new_wifi)) => {
+                let mut changes = wifi_changes(&old_wifi, &new_wifi);
+                let raw = wifi_raw_changes(old_payload, new_payload, &changes)?;
+                changes.extend(raw);
+                changes
+            }
+            _ => raw_changes(old_payload, new_payload)?,
+        };
+
+        diff.changed.push(ChangedPayload {
+            payload: key.clone(),
+            changes,
+        });
+    }
+
+    diff.added = new
+        .iter()
+        .filter(|(id, _)| !old.contains_key(*id))
+        .map(|(_, (key, _))| key.clone())
+        .collect();
+
+    Result::Ok(diff)
+}
+
+impl fmt::Display for PayloadKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} {} ({})",
+            self.PayloadType, self.PayloadIdentifier, self.PayloadUUID
+        )
+    }
+}
+
+impl fmt::Display for ProfileDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for key in &self.removed {
+            writeln!(f, "- {}", key)?;
+        }
+        for key in &self.added {
+            writeln!(f, "+ {}", key)?;
+        }
+        for changed in &self.changed {
+            writeln!(f, "~ {}", changed.payload)?;
+            for change in &changed.changes {
+                writeln!(
+                    f,
+                    "    {}: {} -> {}",
+                    change.field,
+                    change.old.as_deref().unwrap_or("(none)"),
+                    change.new.as_deref().unwrap_or("(none)")
+                )?;
+            }
+        }
+        Result::Ok(())
+    }
+}
@@ -0,0 +1,212 @@
+use serde::Serialize;
+use serde::Serializer;
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum MobileconfError {
+    #[error(
+        "payload[{index}]{}: {source}",
+        .uuid.as_ref().map(|uuid| format!(" (UUID {})", uuid)).unwrap_or_default()
+    )]
+    Payload {
+        index: usize,
+        uuid: Option<String>,
+        source: Box<MobileconfError>,
+    },
+    #[error("certificate {uuid} is not a valid X509: {source}")]
+    InvalidCertificate {
+        uuid: String,
+        source: Box<MobileconfError>,
+    },
+    #[error("missing key: {0}")]
+    MissingKey(String),
+    #[error("expected {expected}: {key}")]
+    WrongType { key: String, expected: &'static str },
+    #[error("not a wifi")]
+    NotWifi,
+    #[error("not a personal wifi")]
+    NotPersonalWifi,
+    #[error("not a TLS certificate")]
+    NotCertificate,
+    #[error("not a PKCS12 identity")]
+    NotPKCS12,
+    #[error("not a VPN")]
+    NotVPN,
+    #[error("not an email account")]
+    NotEmail,
+    #[error("not an Exchange account")]
+    NotExchange,
+    #[error("not a web clip")]
+    NotWebClip,
+    #[error("not a CalDAV account")]
+    NotCalDAV,
+    #[error("not a CardDAV account")]
+    NotCardDAV,
+    #[error("not an LDAP account")]
+    NotLDAP,
+    #[error("not a passcode policy")]
+    NotPasscode,
+    #[error("not a restrictions payload")]
+    NotRestrictions,
+    #[error("not a DNS settings payload")]
+    NotDNS,
+    #[error("not a global HTTP proxy")]
+    NotProxy,
+    #[error("not a SCEP payload")]
+    NotSCEP,
+    #[error("not an AirPrint payload")]
+    NotAirPrint,
+    #[error("not a subscribed calendar")]
+    NotSubscribedCalendar,
+    #[error("not a cellular payload")]
+    NotCellular,
+    #[error("not an extensible SSO payload")]
+    NotExtensibleSSO,
+    #[error("not a content filter")]
+    NotContentFilter,
+    #[error("not a font")]
+    NotFont,
+    #[error("not a managed app configuration")]
+    NotManagedApp,
+    #[error("not a Kerberos SSO payload")]
+    NotSSO,
+    #[error("not an AirPlay security payload")]
+    NotAirPlaySecurity,
+    #[error("not a notification settings payload")]
+    NotNotifications,
+    #[error("not a lock screen message payload")]
+    NotLockScreen,
+    #[error("not a DNS proxy payload")]
+    NotDNSProxy,
+    #[error("not an associated domains payload")]
+    NotAssociatedDomains,
+    #[error("not a home screen layout payload")]
+    NotHomeScreen,
+    #[error("not a certificate transparency payload")]
+    NotCertTransparency,
+    #[error("not a wallpaper")]
+    NotWallpaper,
+    #[error("not a relay payload")]
+    NotRelay,
+    #[error("not an MDM enrollment payload")]
+    NotMDM,
+    #[error("not an app lock payload")]
+    NotAppLock,
+    #[error("no supported EAP type in AcceptEAPTypes: {0:?}")]
+    UnsupportedEAPType(Vec<i64>),
+    #[error("pkcs7: {0}")]
+    Pkcs7(#[from] openssl::error::ErrorStack),
+    #[error("pkcs12: {0}")]
+    Pkcs12(openssl::error::ErrorStack),
+    #[error("x509: {0}")]
+    X509(openssl::error::ErrorStack),
+    #[error("signature verification failed: {0}")]
+    Signature(openssl::error::ErrorStack),
+    #[error("no payload {index}, the profile has {count}")]
+    NoSuchPayload { index: usize, count: usize },
+    #[error("no Apple root certificates were bundled into this build")]
+    NoAppleRoots,
+    #[error("profile has {count} payloads, more than the limit of {max}")]
+    TooManyPayloads { count: usize, max: usize },
+    #[error("profiles nested more than {0} deep")]
+    TooDeeplyNested(usize),
+    #[error("top-level PayloadType is {}, not Configuration", .0.as_deref().unwrap_or("missing"))]
+    NotConfiguration(Option<String>),
+    #[error("profile is not signed")]
+    Unsigned,
+    #[error("{0} embedded certificate(s) expired")]
+    Expired(usize),
+    #[error("{0} embedded certificate(s) expire within {1} days")]
+    Expiring(usize, u32),
+    #[error("no wifi network matches {0}")]
+    NoMatchingWifi(String),
+    #[error("plist: {0}")]
+    Plist(#[from] plist::Error),
+    #[error("io: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("cannot read {}: {}", .path.display(), .source)]
+    Read {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("cannot write {}: {}", .path.display(), .source)]
+    Write {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("gzip: {0}")]
+    Gzip(std::io::Error),
+    #[error("base64: {0}")]
+    Base64(#[from] base64::DecodeError),
+    #[error("json: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("qr code: {0}")]
+    Qr(#[from] qrcode::types::QrError),
+    #[error("yaml: {0}")]
+    Yaml(#[from] serde_yaml::Error),
+    #[error("toml: {0}")]
+    Toml(#[from] toml::ser::Error),
+}
+
+impl MobileconfError {
+    /// Whether a parser turned the payload down for being of another type.
+    pub fn is_wrong_payload_type(&self) -> bool {
+        if let MobileconfError::Payload { source, .. } = self {
+            return source.is_wrong_payload_type();
+        }
+
+        matches!(
+            self,
+            MobileconfError::NotWifi
+                | MobileconfError::NotPersonalWifi
+                | MobileconfError::NotCertificate
+                | MobileconfError::NotPKCS12
+                | MobileconfError::NotVPN
+                | MobileconfError::NotEmail
+                | MobileconfError::NotExchange
+                | MobileconfError::NotWebClip
+                | MobileconfError::NotCalDAV
+                | MobileconfError::NotCardDAV
+                | MobileconfError::NotLDAP
+                | MobileconfError::NotPasscode
+                | MobileconfError::NotRestrictions
+                | MobileconfError::NotDNS
+                | MobileconfError::NotProxy
+                | MobileconfError::NotSCEP
+                | MobileconfError::NotAirPrint
+                | MobileconfError::NotSubscribedCalendar
+                | MobileconfError::NotCellular
+                | MobileconfError::NotExtensibleSSO
+                | MobileconfError::NotContentFilter
+                | MobileconfError::NotFont
+                | MobileconfError::NotManagedApp
+                | MobileconfError::NotSSO
+                | MobileconfError::NotAirPlaySecurity
+                | MobileconfError::NotNotifications
+                | MobileconfError::NotLockScreen
+                | MobileconfError::NotDNSProxy
+                | MobileconfError::NotAssociatedDomains
+                | MobileconfError::NotHomeScreen
+                | MobileconfError::NotCertTransparency
+                | MobileconfError::NotWallpaper
+                | MobileconfError::NotRelay
+                | MobileconfError::NotMDM
+                | MobileconfError::NotAppLock
+        )
+    }
+
+    pub(crate) fn wrong_type(key: &str, expected: &'static str) -> Self {
+        MobileconfError::WrongType {
+            key: key.to_string(),
+            expected,
+        }
+    }
+}
+
+// errors end up in the serialized output as their message
+impl Serialize for MobileconfError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
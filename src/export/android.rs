@@ -0,0 +1,136 @@
+use crate::EAPType;
+use crate::MobileconfWifi;
+use crate::WifiProxy;
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// `WifiEnterpriseConfig.Eap` constants, Android has no LEAP or FAST.
+fn eap_method(eap: EAPType) -> Option<i64> {
+    match eap {
+        EAPType::Peap => Some(0),
+        EAPType::Tls => Some(1),
+        EAPType::Ttls => Some(2),
+        EAPType::Leap | EAPType::Fast => None,
+    }
+}
+
+/// `WifiEnterpriseConfig.Phase2` constants, `None` for TTLS inner methods
+/// Android has no constant for, such as CHAP and EAP.
+fn phase2_method(eap: EAPType, inner: Option<&str>) -> Option<i64> {
+    match (eap, inner) {
+        (EAPType::Ttls, Some("PAP")) => Some(1),
+        (EAPType::Ttls, Some("MSCHAP")) => Some(2),
+        (EAPType::Ttls, Some("MSCHAPv2")) | (EAPType::Peap, _) => Some(3),
+        (EAPType::Ttls, _) => None,
+        _ => Some(0),
+    }
+}
+
+impl MobileconfWifi {
+    /// Render this network as a `<Network>` entry of Android's
+    /// WifiConfigStore.xml, `None` if Android doesn't support its EAP type or
+    /// TTLS inner method.
+    pub fn to_android_network(&self) -> Option<String> {
+        let method = eap_method(self.EAPType)?;
+        let phase2 = phase2_method(self.EAPType, self.TTLSInnerAuthentication.as_deref())?;
+        let ssid = xml_escape(&format!("\"{}\"", self.SSID));
+
+        let mut lines = vec![
+            "<Network>".to_string(),
+            "<WifiConfiguration>".to_string(),
+            format!("<string name=\"ConfigKey\">{}WPA_EAP</string>", ssid),
+            format!("<string name=\"SSID\">{}</string>", ssid),
+            // bits 2 and 3 of the key management set, WPA_EAP and IEEE8021X
+            "<byte-array name=\"AllowedKeyMgmt\" num=\"1\">0c</byte-array>".to_string(),
+            "</WifiConfiguration>".to_string(),
+        ];
+
+        match &self.proxy {
+            Some(WifiProxy::Manual { server, port }) => lines.extend(vec![
+                "<IpConfiguration>".to_string(),
+                "<string name=\"ProxySettings\">STATIC</string>".to_string(),
+                format!("<string name=\"ProxyHost\">{}</string>", xml_escape(server)),
+                format!("<int name=\"ProxyPort\" value=\"{}\" />", port),
+                "</IpConfiguration>".to_string(),
+            ]),
+            Some(WifiProxy::Auto { pac_url }) => lines.extend(vec![
+                "<IpConfiguration>".to_string(),
+                "<string name=\"ProxySettings\">PAC</string>".to_string(),
+                format!("<string name=\"ProxyPac\">{}</string>", xml_escape(pac_url)),
+                "</IpConfiguration>".to_string(),
+            ]),
+            None => {}
+        }
+
+        lines.push("<WifiEnterpriseConfiguration>".to_string());
+
+        if let Some(identity) = &self.UserName {
+            lines.push(format!(
+                "<string name=\"Identity\">{}</string>",
+                xml_escape(identity)
+            ));
+        }
+
+        if let Some(identity) = &self.OuterIdentity {
+            lines.push(format!(
+                "<string name=\"AnonIdentity\">{}</string>",
+                xml_escape(identity)
+            ));
+        }
+
+        // like to_plist_value, the config store needs the real password
+        if let Some(password) = self
+            .UserPassword
+            .as_ref()
+            .filter(|p| !p.expose().is_empty())
+        {
+            lines.push(format!(
+                "<string name=\"Password\">{}</string>",
+                xml_escape(password.expose())
+            ));
+        }
+
+        if !self.TLSTrustedServerNames.is_empty() {
+            // Android matches on a domain suffix, so drop any wildcard label
+            let suffixes: Vec<&str> = self
+                .TLSTrustedServerNames
+                .iter()
+                .map(|name| name.trim_start_matches("*."))
+                .collect();
+            lines.push(format!(
+                "<string name=\"DomSuffixMatch\">{}</string>",
+                xml_escape(&suffixes.join(";"))
+            ));
+        }
+
+        lines.push(format!("<int name=\"EapMethod\" value=\"{}\" />", method));
+        lines.push(format!(
+            "<int name=\"Phase2Method\" value=\"{}\" />",
+            phase2
+        ));
+        lines.push("</WifiEnterpriseConfiguration>".to_string());
+        lines.push("</Network>".to_string());
+        Some(lines.join("\n"))
+    }
+}
+
+/// Wrap `<Network>` entries from `to_android_network` into a complete
+/// WifiConfigStore.xml document.
+pub fn android_config_store(networks: &[String]) -> String {
+    let mut lines = vec![
+        "<?xml version='1.0' encoding='utf-8' standalone='yes' ?>".to_string(),
+        "<WifiConfigStoreData>".to_string(),
+        "<int name=\"Version\" value=\"3\" />".to_string(),
+        "<NetworkList>".to_string(),
+    ];
+    lines.extend(networks.iter().cloned());
+    lines.push("</NetworkList>".to_string());
+    lines.push("</WifiConfigStoreData>".to_string());
+    lines.join("\n")
+}
@@ -0,0 +1,94 @@
+use crate::secret::show_secrets;
+use crate::MobileconfCalDAV;
+use crate::MobileconfCardDAV;
+use crate::Secret;
+
+/// Quote a value for a POSIX shell.
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+/// A `security add-internet-password` invocation for one account. The
+/// password is only filled in when secrets are shown.
+fn add_internet_password(
+    label: &str,
+    host: &str,
+    username: &str,
+    password: Option<&Secret>,
+    port: i64,
+    ssl: bool,
+) -> String {
+    let mut args = vec![
+        "security add-internet-password -U".to_string(),
+        format!("-l {}", shell_quote(label)),
+        format!("-s {}", shell_quote(host)),
+        format!("-a {}", shell_quote(username)),
+        format!("-P {}", port),
+        format!("-r {}", if ssl { "htps" } else { "http" }),
+    ];
+
+    if let Some(password) = password.filter(|_| show_secrets()) {
+        args.push(format!("-w {}", shell_quote(password.expose())));
+    }
+
+    args.join(" ")
+}
+
+impl MobileconfCalDAV {
+    /// Render this account as a `security add-internet-password` command,
+    /// `None` when the profile leaves the username for the user to fill in.
+    pub fn to_keychain_command(&self) -> Option<String> {
+        let username = self.CalDAVUsername.as_ref()?;
+        let label = self
+            .CalDAVAccountDescription
+            .as_deref()
+            .unwrap_or(&self.CalDAVHostName);
+
+        Some(add_internet_password(
+            label,
+            &self.CalDAVHostName,
+            username,
+            self.CalDAVPassword.as_ref(),
+            self.CalDAVPort,
+            self.CalDAVUseSSL,
+        ))
+    }
+}
+
+impl MobileconfCardDAV {
+    /// Render this account as a `security add-internet-password` command,
+    /// `None` when the profile leaves the username for the user to fill in.
+    pub fn to_keychain_command(&self) -> Option<String> {
+        let username = self.CardDAVUsername.as_ref()?;
+        let label = self
+            .CardDAVAccountDescription
+            .as_deref()
+            .unwrap_or(&self.CardDAVHostName);
+
+        Some(add_internet_password(
+            label,
+            &self.CardDAVHostName,
+            username,
+            self.CardDAVPassword.as_ref(),
+            self.CardDAVPort,
+            self.CardDAVUseSSL,
+        ))
+    }
+}
+
+/// A shell script that adds every CalDAV and CardDAV account with a username
+/// to the login keychain.
+pub fn keychain_script(caldavs: &[MobileconfCalDAV], carddavs: &[MobileconfCardDAV]) -> String {
+    let mut lines = vec!["#!/bin/sh".to_string(), "set -e".to_string()];
+    lines.extend(
+        caldavs
+            .iter()
+            .filter_map(MobileconfCalDAV::to_keychain_command),
+    );
+    lines.extend(
+        carddavs
+            .iter()
+            .filter_map(MobileconfCardDAV::to_keychain_command),
+    );
+    lines.join("\n")
+}
@@ -0,0 +1,172 @@
+use crate::ParsedProfile;
+
+/// Keep a value from breaking out of its table cell.
+fn cell(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
+}
+
+/// Keep a value from breaking out of its heading or list item: newlines are
+/// collapsed like in `cell`, and characters Markdown would read as syntax are
+/// escaped.
+fn inline(s: &str) -> String {
+    let collapsed = s
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut escaped = String::with_capacity(collapsed.len());
+    for c in collapsed.chars() {
+        if let '\\' | '`' | '*' | '_' | '[' | ']' | '<' | '>' | '#' | '|' | '~' | '!' = c {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn table(lines: &mut Vec<String>, heading: &str, columns: &[&str], rows: Vec<Vec<String>>) {
+    if rows.is_empty() {
+        return;
+    }
+
+    lines.push(format!("## {}", heading));
+    lines.push(String::new());
+    lines.push(format!("| {} |", columns.join(" | ")));
+    lines.push(format!("|{}", "---|".repeat(columns.len())));
+    for row in rows {
+        let row: Vec<String> = row.iter().map(|value| cell(value)).collect();
+        lines.push(format!("| {} |", row.join(" | ")));
+    }
+    lines.push(String::new());
+}
+
+impl ParsedProfile {
+    /// Render a report of the profile as Markdown: its metadata, then a table
+    /// for each category that has payloads. Secrets are redacted unless
+    /// `set_show_secrets` is on.
+    pub fn to_markdown(&self) -> String {
+        let profile = &self.profile;
+        let mut lines = vec![
+            format!(
+                "# {}",
+                inline(profile.PayloadDisplayName.as_deref().unwrap_or("Profile"))
+            ),
+            String::new(),
+        ];
+
+        let metadata = [
+            ("Identifier", profile.PayloadIdentifier.clone()),
+            ("Organization", profile.PayloadOrganization.clone()),
+            ("Description", profile.PayloadDescription.clone()),
+            ("UUID", profile.PayloadUUID.clone()),
+            ("Version", profile.PayloadVersion.map(|v| v.to_string())),
+            ("Signer", self.signer.clone()),
+        ];
+        for (name, value) in &metadata {
+            if let Some(value) = value {
+                lines.push(format!("- **{}**: {}", name, inline(value)));
+            }
+        }
+        lines.push(String::new());
+
+        table(
+            &mut lines,
+            "Wifi networks",
+            &["SSID", "Identity", "EAP", "Password"],
+            self.wifis
+                .iter()
+                .map(|wifi| {
+                    vec![
+                        wifi.SSID.clone(),
+                        wifi.UserName.clone().unwrap_or_default(),
+                        format!("{:?}", wifi.EAPType),
+                        wifi.UserPassword
+                            .as_ref()
+                            .map(ToString::to_string)
+                            .unwrap_or_default(),
+                    ]
+                })
+                .collect(),
+        );
+
+        table(
+            &mut lines,
+            "Personal wifi networks",
+            &["SSID", "Encryption", "Password"],
+            self.personal_wifis
+                .iter()
+                .map(|wifi| {
+                    vec![
+                        wifi.SSID.clone(),
+                        wifi.EncryptionType.clone(),
+                        wifi.Password
+                            .as_ref()
+                            .map(ToString::to_string)
+                            .unwrap_or_default(),
+                    ]
+                })
+                .collect(),
+        );
+
+        table(
+            &mut lines,
+            "Certificates",
+            &["UUID", "Subject", "Expires"],
+            self.certs
+                .iter()
+                .map(|cert| {
+                    let (subject, expires) = match cert.to_x509() {
+                        Result::Ok(x509) => (
+                            cert.subject().unwrap_or_default(),
+                            x509.not_after().to_string(),
+                        ),
+                        Result::Err(err) => (err.to_string(), String::new()),
+                    };
+                    vec![cert.PayloadUUID.clone(), subject, expires]
+                })
+                .collect(),
+        );
+
+        table(
+            &mut lines,
+            "Identities",
+            &["UUID", "Subject"],
+            self.identities
+                .iter()
+                .map(|identity| {
+                    vec![
+                        identity.PayloadUUID.clone(),
+                        identity.CertificateSubject.clone().unwrap_or_default(),
+                    ]
+                })
+                .collect(),
+        );
+
+        table(
+            &mut lines,
+            "Other payloads",
+            &["Category", "Count"],
+            self.counts()
+                .into_iter()
+                .filter(|&(_, name, count)| {
+                    count > 0
+                        && !matches!(name, "wifis" | "personal_wifis" | "certs" | "identities")
+                })
+                .map(|(_, name, count)| vec![name.to_string(), count.to_string()])
+                .collect(),
+        );
+
+        if !self.errors.is_empty() {
+            lines.push("## Errors".to_string());
+            lines.push(String::new());
+            for err in &self.errors {
+                lines.push(format!("- {}", inline(&err.to_string())));
+            }
+            lines.push(String::new());
+        }
+
+        lines.join("\n")
+    }
+}
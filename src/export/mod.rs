@@ -0,0 +1,11 @@
+mod android;
+mod keychain;
+mod markdown;
+mod networkmanager;
+mod plist;
+mod qr;
+mod wpa_supplicant;
+
+pub use android::android_config_store;
+pub use keychain::keychain_script;
+pub use networkmanager::write_networkmanager;
@@ -0,0 +1,160 @@
+use crate::files::file_name;
+use crate::EAPType;
+use crate::MobileconfError;
+use crate::MobileconfWifi;
+use crate::WifiProxy;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+impl MobileconfWifi {
+    /// Render this network as a NetworkManager keyfile connection.
+    pub fn to_networkmanager(&self) -> String {
+        let mut lines = vec![
+            "[connection]".to_string(),
+            format!("id={}", keyfile_value(&self.SSID)),
+            "type=wifi".to_string(),
+            String::new(),
+            "[wifi]".to_string(),
+            "mode=infrastructure".to_string(),
+            format!("ssid={}", keyfile_value(&self.SSID).replace(';', "\\;")),
+            String::new(),
+            "[wifi-security]".to_string(),
+            "key-mgmt=wpa-eap".to_string(),
+            String::new(),
+            "[802-1x]".to_string(),
+            format!("eap={};", eap_name(self.EAPType)),
+        ];
+
+        if let Some(identity) = &self.UserName {
+            lines.push(format!("identity={}", keyfile_value(identity)));
+        }
+
+        if let Some(identity) = &self.OuterIdentity {
+            lines.push(format!("anonymous-identity={}", keyfile_value(identity)));
+        }
+
+        // the keyfile needs the real password, it's only readable by its owner
+        if let Some(password) = self
+            .UserPassword
+            .as_ref()
+            .filter(|p| !p.expose().is_empty())
+        {
+            lines.push(format!("password={}", keyfile_value(password.expose())));
+        }
+
+        match (self.EAPType, &self.TTLSInnerAuthentication) {
+            (EAPType::Ttls, Some(inner)) if inner == "EAP" => {
+                lines.push("phase2-autheap=mschapv2".to_string())
+            }
+            (EAPType::Ttls, Some(inner)) => {
+                lines.push(format!("phase2-auth={}", inner.to_lowercase()))
+            }
+            (EAPType::Peap, _) => lines.push("phase2-auth=mschapv2".to_string()),
+            _ => {}
+        }
+
+        if !self.TLSTrustedServerNames.is_empty() {
+            let suffixes: Vec<&str> = self
+                .TLSTrustedServerNames
+                .iter()
+                .map(|name| name.trim_start_matches("*."))
+                .collect();
+            lines.push(format!(
+                "domain-suffix-match={}",
+                keyfile_value(&suffixes.join(";"))
+            ));
+        }
+
+        // NetworkManager only knows about auto-config proxies
+        if let Some(WifiProxy::Auto { pac_url }) = &self.proxy {
+            lines.extend(vec![
+                String::new(),
+                "[proxy]".to_string(),
+                "method=auto".to_string(),
+                format!("pac-url={}", keyfile_value(pac_url)),
+            ]);
+        }
+
+        lines.extend(vec![
+            String::new(),
+            "[ipv4]".to_string(),
+            "method=auto".to_string(),
+            String::new(),
+            "[ipv6]".to_string(),
+            "method=auto".to_string(),
+        ]);
+
+        let mut keyfile = lines.join("\n");
+        keyfile.push('\n');
+        keyfile
+    }
+}
+
+/// Escape a value the way GKeyFile reads it back: backslashes and control
+/// characters, and spaces at either end which would be trimmed otherwise.
+fn keyfile_value(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    let last = s.chars().count().saturating_sub(1);
+    for (i, c) in s.chars().enumerate() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            ' ' if i == 0 || i == last => escaped.push_str("\\s"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn eap_name(eap: EAPType) -> &'static str {
+    match eap {
+        EAPType::Tls => "tls",
+        EAPType::Leap => "leap",
+        EAPType::Ttls => "ttls",
+        EAPType::Peap => "peap",
+        EAPType::Fast => "fast",
+    }
+}
+
+/// Write a `<SSID>.nmconnection` keyfile for each network into `dir`. The
+/// files hold credentials so they are only readable by their owner.
+pub fn write_networkmanager(dir: &Path, wifis: &[MobileconfWifi]) -> Result<(), MobileconfError> {
+    fs::create_dir_all(dir).map_err(|source| MobileconfError::Write {
+        path: dir.to_path_buf(),
+        source,
+    })?;
+
+    for wifi in wifis {
+        let path = dir.join(format!("{}.nmconnection", file_name(&wifi.SSID)));
+
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+
+        options
+            .open(&path)
+            .and_then(|mut file| {
+                // mode only applies to new files, an older export may have been
+                // readable by anyone
+                #[cfg(unix)]
+                {
+                    use std::os::unix::fs::PermissionsExt;
+                    file.set_permissions(fs::Permissions::from_mode(0o600))?;
+                }
+                file.write_all(wifi.to_networkmanager().as_bytes())
+            })
+            .map_err(|source| MobileconfError::Write {
+                path: path.clone(),
+                source,
+            })?;
+    }
+
+    Result::Ok(())
+}
@@ -0,0 +1,92 @@
+use crate::MobileconfWifi;
+use crate::WifiProxy;
+use plist::{Dictionary, Value};
+
+fn strings(values: &[String]) -> Value {
+    Value::Array(values.iter().cloned().map(Value::String).collect())
+}
+
+impl MobileconfWifi {
+    /// Rebuild the `com.apple.wifi.managed` payload from the fields we model,
+    /// for writing back out as an unsigned profile. The password is included
+    /// as is, regardless of `set_show_secrets`.
+    pub fn to_plist_value(&self) -> Value {
+        let mut eap = Dictionary::new();
+        if let Some(user) = &self.UserName {
+            eap.insert("UserName".to_string(), Value::String(user.clone()));
+        }
+        if let Some(password) = &self.UserPassword {
+            eap.insert(
+                "UserPassword".to_string(),
+                Value::String(password.expose().to_string()),
+            );
+        }
+        eap.insert(
+            "AcceptEAPTypes".to_string(),
+            Value::Array(vec![Value::Integer(self.EAPType.code().into())]),
+        );
+        eap.insert(
+            "PayloadCertificateAnchorUUID".to_string(),
+            strings(&self.PayloadCertificateAnchorUUID),
+        );
+        if !self.TLSTrustedServerNames.is_empty() {
+            eap.insert(
+                "TLSTrustedServerNames".to_string(),
+                strings(&self.TLSTrustedServerNames),
+            );
+        }
+        if let Some(inner) = &self.TTLSInnerAuthentication {
+            eap.insert(
+                "TTLSInnerAuthentication".to_string(),
+                Value::String(inner.clone()),
+            );
+        }
+        if let Some(identity) = &self.OuterIdentity {
+            eap.insert("OuterIdentity".to_string(), Value::String(identity.clone()));
+        }
+
+        let mut dict = Dictionary::new();
+        dict.insert(
+            "PayloadType".to_string(),
+            Value::String("com.apple.wifi.managed".to_string()),
+        );
+        dict.insert("SSID_STR".to_string(), Value::String(self.SSID.clone()));
+        dict.insert("EAPClientConfiguration".to_string(), Value::Dictionary(eap));
+        if let Some(hotspot) = self.IsHotspot {
+            dict.insert("IsHotspot".to_string(), Value::Boolean(hotspot));
+        }
+        if let Some(name) = &self.DisplayedOperatorName {
+            dict.insert(
+                "DisplayedOperatorName".to_string(),
+                Value::String(name.clone()),
+            );
+        }
+        if let Some(domain) = &self.DomainName {
+            dict.insert("DomainName".to_string(), Value::String(domain.clone()));
+        }
+        if !self.RoamingConsortiumOIs.is_empty() {
+            dict.insert(
+                "RoamingConsortiumOIs".to_string(),
+                strings(&self.RoamingConsortiumOIs),
+            );
+        }
+
+        match &self.proxy {
+            Some(WifiProxy::Manual { server, port }) => {
+                dict.insert("ProxyType".to_string(), Value::String("Manual".to_string()));
+                dict.insert("ProxyServer".to_string(), Value::String(server.clone()));
+                dict.insert(
+                    "ProxyServerPort".to_string(),
+                    Value::Integer((*port).into()),
+                );
+            }
+            Some(WifiProxy::Auto { pac_url }) => {
+                dict.insert("ProxyType".to_string(), Value::String("Auto".to_string()));
+                dict.insert("ProxyPACURL".to_string(), Value::String(pac_url.clone()));
+            }
+            None => {}
+        }
+
+        Value::Dictionary(dict)
+    }
+}
@@ -0,0 +1,60 @@
+use crate::MobileconfError;
+use crate::MobileconfPersonalWifi;
+use qrcode::render::unicode;
+use qrcode::QrCode;
+
+/// Escape the characters that are special in `WIFI:` fields.
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if let '\\' | ';' | ',' | '"' | ':' = c {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+impl MobileconfPersonalWifi {
+    /// The `WIFI:` URI understood by phone cameras, `None` if this network
+    /// can't be represented in one because the profile leaves the password
+    /// for the user to type in.
+    pub fn to_wifi_uri(&self) -> Option<String> {
+        let (typ, password) = match (self.EncryptionType.as_str(), &self.Password) {
+            ("None", _) => ("nopass", None),
+            (_, None) => return None,
+            ("WEP", Some(password)) => ("WEP", Some(password)),
+            // WPA, WPA2, WPA3 and Any all share the WPA type
+            (_, Some(password)) => ("WPA", Some(password)),
+        };
+
+        let mut uri = format!("WIFI:T:{};S:{};", typ, escape(&self.SSID));
+        if let Some(password) = password {
+            // the code is for joining the network, so it needs the real password
+            uri.push_str(&format!("P:{};", escape(password.expose())));
+        }
+        if self.HIDDEN_NETWORK {
+            uri.push_str("H:true;");
+        }
+        uri.push(';');
+
+        Some(uri)
+    }
+
+    /// Render `to_wifi_uri` as a QR code for the terminal.
+    pub fn to_qr_code(&self) -> Result<Option<String>, MobileconfError> {
+        let uri = match self.to_wifi_uri() {
+            Some(uri) => uri,
+            None => return Result::Ok(None),
+        };
+
+        let code = QrCode::new(uri.as_bytes())?;
+        let image = code
+            .render::<unicode::Dense1x2>()
+            .dark_color(unicode::Dense1x2::Light)
+            .light_color(unicode::Dense1x2::Dark)
+            .build();
+
+        Result::Ok(Some(image))
+    }
+}
@@ -0,0 +1,90 @@
+use crate::EAPType;
+use crate::MobileconfWifi;
+
+impl MobileconfWifi {
+    /// Render this network as a wpa_supplicant.conf `network={ ... }` block.
+    pub fn to_wpa_supplicant(&self) -> String {
+        let mut lines = vec![
+            "network={".to_string(),
+            format!("\tssid={}", config_string(&self.SSID)),
+            "\tkey_mgmt=WPA-EAP".to_string(),
+            format!("\teap={}", eap_name(self.EAPType)),
+        ];
+
+        if let Some(identity) = &self.UserName {
+            lines.push(format!("\tidentity={}", config_string(identity)));
+        }
+
+        if let Some(identity) = &self.OuterIdentity {
+            lines.push(format!("\tanonymous_identity={}", config_string(identity)));
+        }
+
+        // a config file needs the real password, redaction is only for
+        // what gets shown on screen
+        if let Some(password) = self
+            .UserPassword
+            .as_ref()
+            .filter(|p| !p.expose().is_empty())
+        {
+            lines.push(format!("\tpassword={}", config_string(password.expose())));
+        }
+
+        let phase2 = match (self.EAPType, &self.TTLSInnerAuthentication) {
+            (EAPType::Ttls, Some(inner)) => Some(phase2_auth(inner)),
+            (EAPType::Peap, _) => Some("auth=MSCHAPV2".to_string()),
+            _ => None,
+        };
+
+        if let Some(phase2) = phase2 {
+            lines.push(format!("\tphase2={}", config_string(&phase2)));
+        }
+
+        if !self.TLSTrustedServerNames.is_empty() {
+            // wpa_supplicant matches on a domain suffix, so drop any wildcard label
+            let suffixes: Vec<&str> = self
+                .TLSTrustedServerNames
+                .iter()
+                .map(|name| name.trim_start_matches("*."))
+                .collect();
+            lines.push(format!(
+                "\tdomain_suffix_match={}",
+                config_string(&suffixes.join(";"))
+            ));
+        }
+
+        lines.push("}".to_string());
+        lines.join("\n")
+    }
+}
+
+/// A string value for wpa_supplicant.conf. Quoted strings can't escape
+/// anything, so values a quote, backslash or control character would break
+/// out of are written in the hex form the parser also accepts.
+fn config_string(s: &str) -> String {
+    if s.chars().any(|c| c == '"' || c == '\\' || c.is_control()) {
+        s.bytes().map(|b| format!("{:02x}", b)).collect()
+    } else {
+        format!("\"{}\"", s)
+    }
+}
+
+fn eap_name(eap: EAPType) -> &'static str {
+    match eap {
+        EAPType::Tls => "TLS",
+        EAPType::Leap => "LEAP",
+        EAPType::Ttls => "TTLS",
+        EAPType::Peap => "PEAP",
+        EAPType::Fast => "FAST",
+    }
+}
+
+fn phase2_auth(inner: &str) -> String {
+    match inner {
+        "PAP" => "auth=PAP".to_string(),
+        "CHAP" => "auth=CHAP".to_string(),
+        "MSCHAP" => "auth=MSCHAP".to_string(),
+        "MSCHAPv2" => "auth=MSCHAPV2".to_string(),
+        "EAP" => "autheap=MSCHAPV2".to_string(),
+        other => format!("auth={}", other.to_uppercase()),
+    }
+}
@@ -0,0 +1,17 @@
+use std::path::{Path, PathBuf};
+
+/// Turn a name taken from the profile, such as an SSID or `PayloadUUID`, into
+/// a single file name, so joining it onto an output directory stays inside
+/// that directory however hostile the profile is.
+pub(crate) fn file_name(name: &str) -> String {
+    let name = name.replace(|c| c == '/' || c == '\\' || c == '\0', "_");
+    match name.as_str() {
+        "" | "." | ".." => "_".repeat(name.len().max(1)),
+        _ => name,
+    }
+}
+
+/// Where `--dump-*` writes a payload's file: `dir/<PayloadUUID>.<extension>`.
+pub(crate) fn payload_file(dir: &Path, uuid: &str, extension: &str) -> PathBuf {
+    dir.join(format!("{}.{}", file_name(uuid), extension))
+}
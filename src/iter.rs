@@ -0,0 +1,62 @@
+use crate::profile::payload_contents;
+use crate::MobileconfError;
+use crate::Parsed;
+use crate::{MobileconfPKCS12, MobileconfPersonalWifi, MobileconfTLSCert, MobileconfWifi};
+use plist::Value;
+
+/// The payloads of a profile plist, empty if it has no `PayloadContent`.
+pub fn payloads(profile: &Value) -> impl Iterator<Item = &Value> {
+    payload_contents(profile).unwrap_or(&[]).iter()
+}
+
+/// Lazily parses the payloads of one type, see [`PayloadIter`].
+pub struct ParsePayloads<I, T> {
+    payloads: I,
+    parse: fn(&Value) -> Result<T, MobileconfError>,
+}
+
+impl<'a, I, T> Iterator for ParsePayloads<I, T>
+where
+    I: Iterator<Item = &'a Value>,
+{
+    type Item = Result<T, MobileconfError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let parse = self.parse;
+        self.payloads
+            .find_map(|payload| Parsed::from(parse(payload)).matched())
+    }
+}
+
+/// Typed adapters over an iterator of payloads. Payloads of other types are
+/// skipped and nothing is parsed until asked for, so callers can stop at the
+/// first match.
+pub trait PayloadIter<'a>: Iterator<Item = &'a Value> + Sized {
+    fn parse_with<T>(
+        self,
+        parse: fn(&Value) -> Result<T, MobileconfError>,
+    ) -> ParsePayloads<Self, T> {
+        ParsePayloads {
+            payloads: self,
+            parse,
+        }
+    }
+
+    fn wifis(self) -> ParsePayloads<Self, MobileconfWifi> {
+        self.parse_with(MobileconfWifi::parse)
+    }
+
+    fn personal_wifis(self) -> ParsePayloads<Self, MobileconfPersonalWifi> {
+        self.parse_with(MobileconfPersonalWifi::parse)
+    }
+
+    fn certs(self) -> ParsePayloads<Self, MobileconfTLSCert> {
+        self.parse_with(MobileconfTLSCert::parse)
+    }
+
+    fn identities(self) -> ParsePayloads<Self, MobileconfPKCS12> {
+        self.parse_with(MobileconfPKCS12::parse)
+    }
+}
+
+impl<'a, I: Iterator<Item = &'a Value>> PayloadIter<'a> for I {}
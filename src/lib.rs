@@ -0,0 +1,62 @@
+//! Extract wifi credentials, settings and certificates from Apple's
+//! `.mobileconfig` file format.
+
+mod anchors;
+mod certinfo;
+mod chains;
+mod dict;
+mod diff;
+mod error;
+mod export;
+mod files;
+mod iter;
+mod payload;
+mod profile;
+mod secret;
+
+pub use anchors::{link_anchors, Anchor};
+pub use certinfo::{days_remaining, CertInfo, Validity};
+pub use chains::{build_chains, CertChain, ChainEnd};
+pub use diff::{diff_profiles, ChangedPayload, FieldChange, PayloadKey, ProfileDiff};
+pub use error::MobileconfError;
+pub use export::*;
+pub use iter::{payloads, ParsePayloads, PayloadIter};
+pub use payload::*;
+pub use profile::{
+    extract_plist, parse_profile, parse_profile_with, payload_types, profile_metadata, raw_payload,
+    signer_info, MobileconfProfile, NestedProfile, ParseOptions, ParsedProfile, Verify,
+    DEFAULT_MAX_PAYLOADS,
+};
+pub use secret::{set_show_secrets, Secret};
+
+pub fn partition_results<A, B, T>(v: T) -> (Vec<A>, Vec<B>)
+where
+    T: Iterator<Item = Result<A, B>>,
+{
+    let mut oks = Vec::new();
+    let mut errs = Vec::new();
+
+    v.for_each(|x| match x {
+        Result::Ok(ok) => oks.push(ok),
+        Result::Err(err) => errs.push(err),
+    });
+
+    (oks, errs)
+}
+
+/// Like `partition_results`, but each item keeps its position in `v` so the
+/// original interleaving of oks and errs can be put back together.
+pub fn partition_results_indexed<A, B, T>(v: T) -> (Vec<(usize, A)>, Vec<(usize, B)>)
+where
+    T: Iterator<Item = Result<A, B>>,
+{
+    let mut oks = Vec::new();
+    let mut errs = Vec::new();
+
+    v.enumerate().for_each(|(index, x)| match x {
+        Result::Ok(ok) => oks.push((index, ok)),
+        Result::Err(err) => errs.push((index, err)),
+    });
+
+    (oks, errs)
+}
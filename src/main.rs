@@ -1,202 +1,1269 @@
-use base64;
-use openssl::pkcs7;
-use openssl::stack;
-use openssl::x509::store;
-use plist::Dictionary;
-use plist::Value;
+use log::info;
+use mobileconf::android_config_store;
+use mobileconf::build_chains;
+use mobileconf::days_remaining;
+use mobileconf::describe_payloads;
+use mobileconf::diff_profiles;
+use mobileconf::dump_certs;
+use mobileconf::dump_fonts;
+use mobileconf::dump_wallpapers;
+use mobileconf::extract_plist;
+use mobileconf::keychain_script;
+use mobileconf::link_anchors;
+use mobileconf::parse_profile_with;
+use mobileconf::payload_types;
+use mobileconf::profile_metadata;
+use mobileconf::raw_payload;
+use mobileconf::signer_info;
+use mobileconf::write_networkmanager;
+use mobileconf::Anchor;
+use mobileconf::CertFormat;
+use mobileconf::ChainEnd;
+use mobileconf::MobileconfError;
+use mobileconf::ParseOptions;
+use mobileconf::ParsedProfile;
+use mobileconf::PayloadKind;
+use mobileconf::Validity;
+use mobileconf::Verify;
+use mobileconf::DEFAULT_MAX_PAYLOADS;
+use owo_colors::OwoColorize;
+use regex::Regex;
+use serde::de::IgnoredAny;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::ffi::OsStr;
+use std::fmt;
 use std::fs;
+use std::io;
 use std::io::Cursor;
-use std::iter;
+use std::io::IsTerminal;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
 use std::path::PathBuf;
+use std::process;
+use std::str::FromStr;
 use std::string::String;
-use std::vec;
+use std::time::Instant;
 use structopt::StructOpt;
-use apply::Apply;
 
 #[derive(Debug, StructOpt)]
 #[structopt(
     name = "mobileconf",
-    about = "Extract pertinent details from .mobileconf files."
+    about = "Extract pertinent details from .mobileconf files.",
+    after_help = "EXIT STATUS:\n    0  every profile parsed and contained at least one payload\n    1  a profile could not be read or parsed\n    2  a profile parsed but contained no payloads, or no PayloadVersion with --profile-version"
 )]
 struct Args {
-    #[structopt(parse(from_os_str))]
-    input: PathBuf,
+    /// Profiles to read, `-` reads from stdin
+    #[structopt(parse(from_os_str), required_unless = "describe")]
+    input: Vec<PathBuf>,
+
+    /// List the payload types we can parse and the fields extracted from each
+    #[structopt(long)]
+    describe: bool,
+
+    /// Output format
+    #[structopt(
+        long,
+        default_value = "debug",
+        possible_values = &["debug", "json", "yaml", "toml", "markdown"]
+    )]
+    format: Format,
+
+    /// Indent JSON output, the default on a terminal
+    #[structopt(long, conflicts_with = "compact")]
+    pretty: bool,
+
+    /// Print JSON on a single line, the default when piped
+    #[structopt(long)]
+    compact: bool,
+
+    /// When to color the debug output
+    #[structopt(
+        long,
+        value_name = "WHEN",
+        default_value = "auto",
+        possible_values = &["auto", "always", "never"]
+    )]
+    color: ColorWhen,
+
+    /// Export wifi networks in another tool's config format instead, or
+    /// CalDAV/CardDAV accounts as a macOS keychain script
+    #[structopt(
+        long,
+        possible_values = &["wpa-supplicant", "networkmanager", "android", "keychain"]
+    )]
+    export: Option<Export>,
+
+    /// Directory to write exported connection files to
+    #[structopt(
+        long,
+        value_name = "DIR",
+        parse(from_os_str),
+        required_if("export", "networkmanager")
+    )]
+    export_dir: Option<PathBuf>,
+
+    /// Write each embedded certificate to DIR/<PayloadUUID>.<pem|der>
+    #[structopt(long, value_name = "DIR", parse(from_os_str))]
+    dump_certs: Option<PathBuf>,
+
+    /// Encoding of the certificates written by --dump-certs
+    #[structopt(long, default_value = "pem", possible_values = &["pem", "der"])]
+    cert_format: CertFormat,
+
+    /// Write each embedded font to DIR/<PayloadUUID>.<ttf|otf|ttc>
+    #[structopt(long, value_name = "DIR", parse(from_os_str))]
+    dump_fonts: Option<PathBuf>,
+
+    /// Write each wallpaper image to DIR/<PayloadUUID>.<png|jpg>
+    #[structopt(long, value_name = "DIR", parse(from_os_str))]
+    dump_wallpaper: Option<PathBuf>,
+
+    /// Print passwords and other secrets instead of redacting them
+    #[structopt(long)]
+    show_secrets: bool,
+
+    /// Verify the profile signature against the system CA store
+    #[structopt(long)]
+    verify: bool,
+
+    /// Verify against the certificates in this PEM file instead
+    #[structopt(long, value_name = "FILE", parse(from_os_str), requires = "verify")]
+    ca_file: Option<PathBuf>,
+
+    /// Verify against the Apple root CAs bundled with mobileconf instead
+    #[structopt(long, requires = "verify", conflicts_with = "ca-file")]
+    apple_roots: bool,
+
+    /// Print the certificates that signed the profile and exit
+    #[structopt(long)]
+    signer_info: bool,
+
+    /// Print the plist inside the profile and exit, `--dump-plist=xml`
+    /// converts binary plists to XML
+    #[structopt(
+        long,
+        value_name = "FORMAT",
+        require_equals = true,
+        possible_values = &["raw", "xml"]
+    )]
+    dump_plist: Option<Option<PlistFormat>>,
+
+    /// Only print how many payloads of each category and errors were found
+    #[structopt(long)]
+    count: bool,
+
+    /// Print how many payloads of each PayloadType the profile has and exit
+    #[structopt(long)]
+    list_types: bool,
+
+    /// Print the profile's PayloadVersion and exit, 0 if it has none
+    #[structopt(long)]
+    profile_version: bool,
+
+    /// Print the Nth entry of PayloadContent as is, XML unless --format asks
+    /// for something else
+    #[structopt(long, value_name = "N")]
+    payload: Option<usize>,
+
+    /// Print the payloads added, removed or changed in FILE compared to the
+    /// input
+    #[structopt(long, value_name = "FILE", parse(from_os_str))]
+    diff: Option<PathBuf>,
+
+    /// Only parse and show payloads of this type, can be repeated
+    #[structopt(
+        long,
+        value_name = "TYPE",
+        number_of_values = 1,
+        possible_values = &[
+            "wifi", "cert", "vpn", "email", "exchange", "webclip", "caldav", "carddav", "ldap",
+            "passcode", "restrictions", "dns", "proxy", "scep", "airprint", "subscribedcalendar",
+            "cellular", "extensiblesso", "contentfilter", "font", "managedapp", "sso", "airplay",
+            "notifications", "lockscreen", "dnsproxy", "associateddomains", "homescreen",
+            "certtransparency", "wallpaper", "relay", "mdm", "applock"
+        ]
+    )]
+    only: Vec<PayloadKind>,
+
+    /// Only keep the wifi network with this SSID
+    #[structopt(long, value_name = "NAME")]
+    ssid: Option<String>,
+
+    /// Only keep wifi networks whose SSID matches this regex
+    #[structopt(
+        long,
+        value_name = "REGEX",
+        parse(try_from_str = Regex::new),
+        conflicts_with = "ssid"
+    )]
+    ssid_regex: Option<Regex>,
+
+    /// Treat certificate payloads that aren't valid X509, and profiles whose
+    /// top-level PayloadType isn't Configuration, as errors
+    #[structopt(long)]
+    strict: bool,
+
+    /// Refuse profiles with more than N payloads [default: 10000]
+    #[structopt(long, value_name = "N")]
+    max_payloads: Option<usize>,
+
+    /// Only show parsed payloads and errors, leaving out the profile metadata
+    #[structopt(short, long)]
+    quiet: bool,
+
+    /// Log what each stage does to stderr, repeat for more detail. RUST_LOG
+    /// works too
+    #[structopt(short, long, parse(from_occurrences))]
+    verbose: u8,
+
+    /// Print a QR code for joining each WPA/WEP personal or open wifi network
+    #[structopt(long)]
+    qr: bool,
+
+    /// Report whether each embedded certificate is currently valid
+    #[structopt(long)]
+    check_expiry: bool,
+
+    /// List embedded certificates that expire within DAYS from now
+    #[structopt(long, value_name = "DAYS")]
+    expiring_within: Option<u32>,
+
+    /// Write output to FILE instead of stdout
+    #[structopt(short, long, value_name = "FILE", parse(from_os_str))]
+    output: Option<PathBuf>,
+
+    /// Read every *.mobileconfig file below directories given as input. With
+    /// several files each one's output is tagged with its path, json, yaml
+    /// and toml by wrapping it as {path, result}
+    #[structopt(short, long)]
+    recursive: bool,
+}
+
+impl Args {
+    fn parse_options(&self) -> ParseOptions {
+        let verify = match (self.verify, &self.ca_file) {
+            (false, _) => Verify::No,
+            (true, _) if self.apple_roots => Verify::AppleRoots,
+            (true, None) => Verify::SystemRoots,
+            (true, Some(path)) => Verify::CaFile(path.clone()),
+        };
+
+        ParseOptions {
+            verify,
+            only: self.only.clone(),
+            strict: self.strict,
+            max_payloads: self.max_payloads.unwrap_or(DEFAULT_MAX_PAYLOADS),
+        }
+    }
+
+    fn ssid_filter(&self) -> Option<SsidFilter> {
+        match (&self.ssid, &self.ssid_regex) {
+            (Some(ssid), _) => Some(SsidFilter::Exact(ssid)),
+            (None, Some(regex)) => Some(SsidFilter::Regex(regex)),
+            (None, None) => None,
+        }
+    }
+
+    fn to_json<T: Serialize>(&self, value: &T) -> Result<String, MobileconfError> {
+        let pretty = if self.pretty || self.compact {
+            self.pretty
+        } else {
+            self.output.is_none() && io::stdout().is_terminal()
+        };
+
+        if pretty {
+            Result::Ok(serde_json::to_string_pretty(value)?)
+        } else {
+            Result::Ok(serde_json::to_string(value)?)
+        }
+    }
+
+    /// Color only makes sense for the debug format on a terminal.
+    fn use_color(&self) -> bool {
+        matches!(self.format, Format::Debug)
+            && match self.color {
+                ColorWhen::Always => true,
+                ColorWhen::Never => false,
+                ColorWhen::Auto => self.output.is_none() && io::stdout().is_terminal(),
+            }
+    }
 }
 
-#[allow(non_snake_case)]
 #[derive(Debug)]
-struct MobileconfWifi {
-    // pointer to the certificate to use for this connection
-    PayloadCertificateAnchorUUID: Vec<String>,
-    TLSTrustedServerNames: Vec<String>,
-    UserName: String,
-    UserPassword: String,
-    SSID: String,
-    TTLSInnerAuthentication: String,
-}
-
-fn get_string(dict: &Dictionary, key: &str) -> Result<String, String> {
-    dict.get(key)
-        .ok_or(format!("missing key: {}", key))?
-        .as_string()
-        .ok_or(format!("key not a string: {}", key))
-        .map(str::to_string)
-}
-
-#[allow(non_snake_case)]
-impl MobileconfWifi {
-    #[allow(non_snake_case)]
-    fn parse(v: &Value) -> Result<Self, String> {
-        let dict = v.as_dictionary().expect("");
-
-        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
-            if typ != *"com.apple.wifi.managed" {
-                return Result::Err("Not a wifi".to_string());
-            }
-        }
-
-        let EAPClientConfiguration = dict
-            .get("EAPClientConfiguration")
-            .ok_or("no EAPClientConfiguration")?
-            .as_dictionary()
-            .ok_or("EAPClientConfiguration not a dict")?;
-
-        let PayloadCertificateAnchorUUID = EAPClientConfiguration
-            .get("PayloadCertificateAnchorUUID")
-            .ok_or("no PayloadCertificateAnchorUUID")?
-            .as_array()
-            .ok_or("expected array: PayloadCertificateAnchorUUID")?
-            .iter()
-            .filter_map(Value::as_string)
-            .map(str::to_string)
-            .collect();
+enum Format {
+    Debug,
+    Json,
+    Yaml,
+    Toml,
+    Markdown,
+}
 
-        let TLSTrustedServerNames = match EAPClientConfiguration.get("TLSTrustedServerNames") {
-            Some(tls_servers) => tls_servers
-                .as_array()
-                .ok_or("expected array: TLSTrustedServerNames")
-                .map(|vec| {
-                    vec.iter()
-                        .filter_map(Value::as_string)
-                        .map(str::to_string)
-                        .collect()
-                }),
-            None => Result::Ok(Vec::new()),
-        }?;
+impl FromStr for Format {
+    type Err = String;
 
-        let UserName = get_string(EAPClientConfiguration, "UserName")?;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "debug" => Result::Ok(Format::Debug),
+            "json" => Result::Ok(Format::Json),
+            "yaml" => Result::Ok(Format::Yaml),
+            "toml" => Result::Ok(Format::Toml),
+            "markdown" => Result::Ok(Format::Markdown),
+            _ => Result::Err(format!("unknown format: {}", s)),
+        }
+    }
+}
 
-        let UserPassword = get_string(EAPClientConfiguration, "UserPassword")?;
+#[derive(Debug)]
+enum ColorWhen {
+    Auto,
+    Always,
+    Never,
+}
 
-        let SSID = get_string(dict, "SSID_STR")?;
+impl FromStr for ColorWhen {
+    type Err = String;
 
-        let TTLSInnerAuthentication =
-            get_string(EAPClientConfiguration, "TTLSInnerAuthentication")?;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Result::Ok(ColorWhen::Auto),
+            "always" => Result::Ok(ColorWhen::Always),
+            "never" => Result::Ok(ColorWhen::Never),
+            _ => Result::Err(format!("unknown color choice: {}", s)),
+        }
+    }
+}
 
-        Result::Ok(MobileconfWifi {
-            PayloadCertificateAnchorUUID,
-            TLSTrustedServerNames,
-            UserName,
-            UserPassword,
-            SSID,
-            TTLSInnerAuthentication,
-        })
+#[derive(Debug)]
+enum Export {
+    WpaSupplicant,
+    NetworkManager,
+    Android,
+    Keychain,
+}
+
+impl FromStr for Export {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "wpa-supplicant" => Result::Ok(Export::WpaSupplicant),
+            "networkmanager" => Result::Ok(Export::NetworkManager),
+            "android" => Result::Ok(Export::Android),
+            "keychain" => Result::Ok(Export::Keychain),
+            _ => Result::Err(format!("unknown export: {}", s)),
+        }
     }
 }
 
-#[allow(non_snake_case)]
 #[derive(Debug)]
-struct MobileconfTLSCert {
-    PayloadUUID: String,
-    // tls cert bytes
-    PayloadContent: String,
+enum PlistFormat {
+    Raw,
+    Xml,
+}
+
+impl FromStr for PlistFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "raw" => Result::Ok(PlistFormat::Raw),
+            "xml" => Result::Ok(PlistFormat::Xml),
+            _ => Result::Err(format!("unknown plist format: {}", s)),
+        }
+    }
 }
 
-impl MobileconfTLSCert {
-    #[allow(non_snake_case)]
-    fn parse(v: &Value) -> Result<Self, String> {
-        let dict = v.as_dictionary().expect("");
+/// Whether a string looks like a long base64 blob, such as certificate data.
+fn is_blob(s: &str) -> bool {
+    s.len() > 76
+        && s.bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'+' || b == b'/' || b == b'=')
+}
+
+/// Break up base64 blobs so the YAML emitter writes them as block scalars.
+fn wrap_blobs(value: &mut serde_yaml::Value) {
+    match value {
+        serde_yaml::Value::String(s) if is_blob(s.as_str()) => {
+            let lines: Vec<&str> = s
+                .as_bytes()
+                .chunks(76)
+                // base64 is always ascii
+                .map(|line| std::str::from_utf8(line).unwrap_or_default())
+                .collect();
+            *s = lines.join("\n");
+        }
+        serde_yaml::Value::Sequence(seq) => seq.iter_mut().for_each(wrap_blobs),
+        serde_yaml::Value::Mapping(map) => map.values_mut().for_each(wrap_blobs),
+        _ => {}
+    }
+}
 
-        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
-            if !(typ == *"com.apple.security.pem" || typ == *"com.apple.security.root") {
-                return Result::Err("Not a TLS certificate".to_string());
+fn to_yaml<T: Serialize>(value: &T) -> Result<String, MobileconfError> {
+    let mut value = serde_yaml::to_value(value)?;
+    wrap_blobs(&mut value);
+    Result::Ok(serde_yaml::to_string(&value)?)
+}
+
+/// Which wifi networks `--ssid` or `--ssid-regex` asked for.
+enum SsidFilter<'a> {
+    Exact(&'a str),
+    Regex(&'a Regex),
+}
+
+impl SsidFilter<'_> {
+    fn matches(&self, ssid: &str) -> bool {
+        match self {
+            SsidFilter::Exact(wanted) => ssid == *wanted,
+            SsidFilter::Regex(regex) => regex.is_match(ssid),
+        }
+    }
+}
+
+impl fmt::Display for SsidFilter<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SsidFilter::Exact(ssid) => write!(f, "SSID {:?}", ssid),
+            SsidFilter::Regex(regex) => write!(f, "/{}/", regex),
+        }
+    }
+}
+
+/// Drop the wifis whose SSID `keep` rejects, here and in nested profiles.
+/// Returns how many are left.
+fn retain_wifis(parsed: &mut ParsedProfile, keep: &dyn Fn(&str) -> bool) -> usize {
+    parsed.wifis.retain(|wifi| keep(&wifi.SSID));
+    parsed.personal_wifis.retain(|wifi| keep(&wifi.SSID));
+    parsed.wifis.len()
+        + parsed.personal_wifis.len()
+        + parsed
+            .nested
+            .iter_mut()
+            .map(|nested| retain_wifis(&mut nested.profile, keep))
+            .sum::<usize>()
+}
+
+/// The certs of the profile as chains from leaf to root, each issuer indented
+/// under the cert it issued.
+fn write_chains(out: &mut dyn Write, parsed: &ParsedProfile) -> io::Result<()> {
+    // a lone cert is its own chain, nothing to report
+    if parsed.certs.len() < 2 {
+        return Result::Ok(());
+    }
+
+    writeln!(out, "Chains:")?;
+    for chain in build_chains(&parsed.certs) {
+        let mut indent = String::from("    ");
+        for cert in &chain.certs {
+            let subject = cert
+                .subject()
+                .unwrap_or_else(|err| format!("{} ({})", cert.PayloadUUID, err));
+            writeln!(out, "{}{} ({})", indent, subject, cert.PayloadUUID)?;
+            indent.push_str("    ");
+        }
+        match chain.end {
+            ChainEnd::Root => {}
+            ChainEnd::MissingIssuer(issuer) => {
+                writeln!(out, "{}broken: missing issuer {}", indent, issuer)?
             }
+            ChainEnd::Loop => writeln!(out, "{}broken: issuers loop", indent)?,
         }
+    }
 
-        let PayloadUUID = get_string(dict, "PayloadUUID")?;
+    Result::Ok(())
+}
 
-        let data: &[u8] = dict
-            .get("PayloadContent")
-            .ok_or("missing key: PayloadContent")?
-            .as_data()
-            .ok_or("expected data")?;
+/// For each wifi, the subjects of the certs its anchor UUIDs point at.
+fn write_anchors(out: &mut dyn Write, parsed: &ParsedProfile) -> io::Result<()> {
+    let linked = link_anchors(&parsed.wifis, &parsed.certs);
+    if linked.iter().all(|(_, anchors)| anchors.is_empty()) {
+        return Result::Ok(());
+    }
 
-        let PayloadContent = base64::encode(data);
+    writeln!(out, "Anchors:")?;
+    for (wifi, anchors) in linked {
+        for anchor in anchors {
+            match anchor {
+                Anchor::Resolved(cert) => {
+                    // an undecodable cert shouldn't hide the rest of the dump
+                    let subject = cert
+                        .subject()
+                        .unwrap_or_else(|err| format!("{} ({})", cert.PayloadUUID, err));
+                    writeln!(out, "    {}: {}", wifi.SSID, subject)?
+                }
+                Anchor::Missing(uuid) => {
+                    writeln!(out, "    {}: missing anchor {}", wifi.SSID, uuid)?
+                }
+            }
+        }
+    }
 
-        Result::Ok(MobileconfTLSCert {
-            PayloadUUID,
-            PayloadContent,
-        })
+    Result::Ok(())
+}
+
+/// The uncolored debug dump of a profile, with each nested profile's dump
+/// indented below it. `write_debug` adds the highlighting.
+fn write_debug_dump(
+    buf: &mut Vec<u8>,
+    parsed: &ParsedProfile,
+    options: &ParseOptions,
+    args: &Args,
+) -> Result<(), MobileconfError> {
+    if args.quiet {
+        if !parsed.errors.is_empty() {
+            writeln!(buf, "Errs: {:?}", parsed.errors)?;
+        }
+    } else {
+        if let Some(signer) = &parsed.signer {
+            writeln!(buf, "Verified signer: {}", signer)?;
+        }
+        if parsed.signers > 1 {
+            writeln!(buf, "Signers: {}", parsed.signers)?;
+        }
+        writeln!(buf, "Profile: {:#?}", parsed.profile)?;
+        writeln!(buf, "Errs: {:?}", parsed.errors)?;
+    }
+    if options.wants(PayloadKind::Wifi) {
+        writeln!(buf, "Found wifis: {:#?}", parsed.wifis)?;
+        write_anchors(buf, parsed)?;
+        writeln!(buf, "Found personal wifis: {:#?}", parsed.personal_wifis)?;
+    }
+    if options.wants(PayloadKind::Cert) {
+        writeln!(buf, "Found certs: {:#?}", parsed.certs)?;
+        write_chains(buf, parsed)?;
+        writeln!(buf, "Found identities: {:#?}", parsed.identities)?;
+    }
+    if options.wants(PayloadKind::Vpn) {
+        writeln!(buf, "Found vpns: {:#?}", parsed.vpns)?;
+    }
+    if options.wants(PayloadKind::Email) {
+        writeln!(buf, "Found emails: {:#?}", parsed.emails)?;
+    }
+    if options.wants(PayloadKind::Exchange) {
+        writeln!(buf, "Found exchange accounts: {:#?}", parsed.exchanges)?;
+    }
+    if options.wants(PayloadKind::WebClip) {
+        writeln!(buf, "Found webclips: {:#?}", parsed.webclips)?;
+    }
+    if options.wants(PayloadKind::CalDav) {
+        writeln!(buf, "Found caldav accounts: {:#?}", parsed.caldavs)?;
+    }
+    if options.wants(PayloadKind::CardDav) {
+        writeln!(buf, "Found carddav accounts: {:#?}", parsed.carddavs)?;
+    }
+    if options.wants(PayloadKind::Ldap) {
+        writeln!(buf, "Found ldap accounts: {:#?}", parsed.ldaps)?;
     }
+    if options.wants(PayloadKind::Passcode) {
+        writeln!(buf, "Found passcode policies: {:#?}", parsed.passcodes)?;
+    }
+    if options.wants(PayloadKind::Restrictions) {
+        writeln!(buf, "Found restrictions: {:#?}", parsed.restrictions)?;
+    }
+    if options.wants(PayloadKind::Dns) {
+        writeln!(buf, "Found dns settings: {:#?}", parsed.dns)?;
+    }
+    if options.wants(PayloadKind::Proxy) {
+        writeln!(buf, "Found proxies: {:#?}", parsed.proxies)?;
+    }
+    if options.wants(PayloadKind::Scep) {
+        writeln!(buf, "Found scep: {:#?}", parsed.sceps)?;
+    }
+    if options.wants(PayloadKind::AirPrint) {
+        writeln!(buf, "Found airprint printers: {:#?}", parsed.airprints)?;
+    }
+    if options.wants(PayloadKind::SubscribedCalendar) {
+        writeln!(
+            buf,
+            "Found subscribed calendars: {:#?}",
+            parsed.subscribed_calendars
+        )?;
+    }
+    if options.wants(PayloadKind::Cellular) {
+        writeln!(buf, "Found cellular settings: {:#?}", parsed.cellulars)?;
+    }
+    if options.wants(PayloadKind::ExtensibleSso) {
+        writeln!(buf, "Found extensible sso: {:#?}", parsed.extensible_ssos)?;
+    }
+    if options.wants(PayloadKind::ContentFilter) {
+        writeln!(buf, "Found content filters: {:#?}", parsed.content_filters)?;
+    }
+    if options.wants(PayloadKind::Font) {
+        writeln!(buf, "Found fonts: {:#?}", parsed.fonts)?;
+    }
+    if options.wants(PayloadKind::ManagedApp) {
+        writeln!(buf, "Found managed apps: {:#?}", parsed.managed_apps)?;
+    }
+    if options.wants(PayloadKind::Sso) {
+        writeln!(buf, "Found sso: {:#?}", parsed.ssos)?;
+    }
+    if options.wants(PayloadKind::AirPlay) {
+        writeln!(buf, "Found airplay settings: {:#?}", parsed.airplays)?;
+    }
+    if options.wants(PayloadKind::Notifications) {
+        writeln!(
+            buf,
+            "Found notification settings: {:#?}",
+            parsed.notifications
+        )?;
+    }
+    if options.wants(PayloadKind::LockScreen) {
+        writeln!(buf, "Found lock screen messages: {:#?}", parsed.lockscreens)?;
+    }
+    if options.wants(PayloadKind::DnsProxy) {
+        writeln!(buf, "Found dns proxies: {:#?}", parsed.dns_proxies)?;
+    }
+    if options.wants(PayloadKind::AssociatedDomains) {
+        writeln!(
+            buf,
+            "Found associated domains: {:#?}",
+            parsed.associated_domains
+        )?;
+    }
+    if options.wants(PayloadKind::HomeScreen) {
+        writeln!(buf, "Found home screen layouts:")?;
+        for layout in &parsed.home_screens {
+            write!(buf, "{}", layout)?;
+        }
+    }
+    if options.wants(PayloadKind::CertTransparency) {
+        writeln!(
+            buf,
+            "Found certificate transparency settings: {:#?}",
+            parsed.cert_transparencies
+        )?;
+    }
+    if options.wants(PayloadKind::Wallpaper) {
+        writeln!(buf, "Found wallpapers: {:#?}", parsed.wallpapers)?;
+    }
+    if options.wants(PayloadKind::Relay) {
+        writeln!(buf, "Found relays: {:#?}", parsed.relays)?;
+    }
+    if options.wants(PayloadKind::Mdm) {
+        writeln!(buf, "Found mdm enrollments: {:#?}", parsed.mdms)?;
+    }
+    if options.wants(PayloadKind::AppLock) {
+        writeln!(
+            buf,
+            "Found single app mode settings: {:#?}",
+            parsed.app_locks
+        )?;
+    }
+
+    for nested in &parsed.nested {
+        writeln!(buf, "Nested profile in payload[{}]:", nested.index)?;
+        let mut inner = Vec::new();
+        write_debug_dump(&mut inner, &nested.profile, options, args)?;
+        for line in String::from_utf8_lossy(&inner).lines() {
+            writeln!(buf, "    {}", line)?;
+        }
+    }
+
+    Result::Ok(())
 }
 
-fn partition_results<A, B, T>(v: T) -> (Vec<A>, Vec<B>)
-where
-    T: iter::Iterator<Item = Result<A, B>>,
-{
-    let mut oks = Vec::new();
-    let mut errs = Vec::new();
+/// Write a debug dump, highlighting SSIDs, redacted secrets and errors.
+fn write_debug(out: &mut dyn Write, text: &[u8], color: bool) -> io::Result<()> {
+    if !color {
+        return out.write_all(text);
+    }
 
-    v.for_each(|x| match x {
-        Result::Ok(ok) => oks.push(ok),
-        Result::Err(err) => errs.push(err),
-    });
+    for line in String::from_utf8_lossy(text).lines() {
+        let trimmed = line.trim_start();
+        let indent = &line[..line.len() - trimmed.len()];
 
-    (oks, errs)
+        if trimmed.starts_with("Errs: ") && trimmed != "Errs: []" {
+            writeln!(out, "{}{}", indent, trimmed.red())?;
+        } else if trimmed.starts_with("SSID: ") {
+            writeln!(out, "{}{}", indent, trimmed.bold())?;
+        } else if line.contains("<redacted>") {
+            let dimmed = "<redacted>".dimmed().to_string();
+            writeln!(out, "{}", line.replace("<redacted>", &dimmed))?;
+        } else {
+            writeln!(out, "{}", line)?;
+        }
+    }
+
+    Result::Ok(())
 }
 
-fn main() {
-    let args = Args::from_args();
+fn counts_map<'a>(counts: &[(&'a str, usize)]) -> BTreeMap<&'a str, usize> {
+    counts.iter().cloned().collect()
+}
 
-    let bytes = fs::read(args.input).expect("read mobileconf");
-    let p7 = pkcs7::Pkcs7::from_der(&bytes[..]).expect("read pkcs7");
+fn to_toml<T: Serialize>(value: &T) -> Result<String, MobileconfError> {
+    // going through toml::Value skips None fields and puts plain values
+    // ahead of tables, which serializing the structs directly can't do
+    let value = toml::Value::try_from(value)?;
+    Result::Ok(toml::to_string_pretty(&value)?)
+}
+
+fn read_input(path: &Path) -> io::Result<Vec<u8>> {
+    if path == Path::new("-") {
+        // stdin can't be seeked, so buffer all of it up front
+        let mut bytes = Vec::new();
+        io::stdin().read_to_end(&mut bytes)?;
+        Result::Ok(bytes)
+    } else {
+        fs::read(path)
+    }
+}
+
+/// Every `*.mobileconfig` file below `dir`, in a stable order. Entries that
+/// can't be read are returned as errors so the rest of the walk carries on.
+fn find_profiles(dir: &Path) -> Vec<Result<PathBuf, MobileconfError>> {
+    let read_error = |source| MobileconfError::Read {
+        path: dir.to_path_buf(),
+        source,
+    };
+
+    let entries = match fs::read_dir(dir) {
+        Result::Ok(entries) => entries,
+        Result::Err(err) => return vec![Result::Err(read_error(err))],
+    };
+
+    let mut found = Vec::new();
+    let mut children = Vec::new();
+    for entry in entries {
+        // don't follow symlinked directories, they can loop
+        match entry.and_then(|e| Result::Ok((e.path(), e.file_type()?.is_dir()))) {
+            Result::Ok(child) => children.push(child),
+            Result::Err(err) => found.push(Result::Err(read_error(err))),
+        }
+    }
+    children.sort();
+
+    for (path, is_dir) in children {
+        if is_dir {
+            found.extend(find_profiles(&path));
+        } else if path.extension() == Some(OsStr::new("mobileconfig")) {
+            found.push(Result::Ok(path));
+        }
+    }
+
+    found
+}
+
+/// Write the parse errors to stderr, as one JSON object per line for `--format json`.
+fn report_errors(path: &Path, parsed: &ParsedProfile, args: &Args) -> Result<(), MobileconfError> {
+    let stderr = io::stderr();
+    let mut stderr = stderr.lock();
+
+    for err in &parsed.errors {
+        if let Format::Json = args.format {
+            let line =
+                serde_json::json!({ "path": path.display().to_string(), "error": err.to_string() });
+            writeln!(stderr, "{}", line)?;
+        } else {
+            writeln!(stderr, "error: {}: {}", path.display(), err)?;
+        }
+    }
+
+    for nested in &parsed.nested {
+        report_errors(path, &nested.profile, args)?;
+    }
+
+    Result::Ok(())
+}
+
+/// With several inputs, say which one `buf` came from. Serialized documents
+/// are wrapped in one holding the path and the result, so the output stays
+/// parseable; anything else gets a `==> name <==` header.
+fn write_tagged(
+    out: &mut dyn Write,
+    name: &Path,
+    buf: &[u8],
+    args: &Args,
+) -> Result<(), MobileconfError> {
+    if buf.is_empty() {
+        return Result::Ok(());
+    }
+    let path = name.display().to_string();
+    let untagged = |out: &mut dyn Write| -> io::Result<()> {
+        writeln!(out, "==> {} <==", path)?;
+        out.write_all(buf)
+    };
+
+    match args.format {
+        // spliced in as is, parsing it again would reorder the keys
+        Format::Json if serde_json::from_slice::<IgnoredAny>(buf).is_ok() => {
+            writeln!(
+                out,
+                "{{\"path\":{},\"result\":{}}}",
+                serde_json::to_string(&path)?,
+                String::from_utf8_lossy(buf).trim_end()
+            )?;
+        }
+        Format::Yaml if serde_yaml::from_slice::<IgnoredAny>(buf).is_ok() => {
+            write!(out, "---\npath: {}result:\n", serde_yaml::to_string(&path)?)?;
+            for line in String::from_utf8_lossy(buf).lines() {
+                if line.is_empty() {
+                    writeln!(out)?;
+                } else {
+                    writeln!(out, "  {}", line)?;
+                }
+            }
+        }
+        // every file adds to the same [[files]] array
+        Format::Toml => match toml::from_slice::<toml::Value>(buf) {
+            Result::Ok(result) => {
+                let mut file = BTreeMap::new();
+                file.insert("path", toml::Value::String(path.clone()));
+                file.insert("result", result);
+                let mut table = BTreeMap::new();
+                table.insert("files", vec![file]);
+                write!(out, "{}", to_toml(&table)?)?;
+            }
+            Result::Err(_) => untagged(out)?,
+        },
+        Format::Markdown => {
+            writeln!(out, "File: `{}`\n", path)?;
+            out.write_all(buf)?;
+        }
+        _ => untagged(out)?,
+    }
+
+    Result::Ok(())
+}
+
+fn describe(args: &Args, out: &mut dyn Write) -> Result<(), MobileconfError> {
+    let payloads = describe_payloads();
+
+    match args.format {
+        Format::Debug | Format::Markdown => {
+            for payload in &payloads {
+                writeln!(
+                    out,
+                    "{} ({})",
+                    payload.name,
+                    payload.payload_types.join(", ")
+                )?;
+                for field in &payload.fields {
+                    writeln!(out, "    {}: {}", field.name, field.typ)?;
+                }
+            }
+        }
+        Format::Json => writeln!(out, "{}", args.to_json(&payloads)?)?,
+        Format::Yaml => write!(out, "{}", to_yaml(&payloads)?)?,
+        Format::Toml => {
+            // toml documents have to be a table at the top
+            let mut table = BTreeMap::new();
+            table.insert("payloads", payloads);
+            write!(out, "{}", to_toml(&table)?)?
+        }
+    }
 
-    // we just want to get the payload, these inputs gets us that.
+    Result::Ok(())
+}
 
-    let stack = stack::Stack::new().expect("new cert stack");
+/// Handle a single input, returning whether it contained any payloads.
+fn run(path: &Path, args: &Args, out: &mut dyn Write) -> Result<bool, MobileconfError> {
+    let bytes = read_input(path)?;
+    info!("{}: read {} bytes", path.display(), bytes.len());
 
-    let store = store::X509StoreBuilder::new()
-        .expect("new cert store")
-        .build();
+    if args.signer_info {
+        let signers = signer_info(&bytes)?;
+
+        match args.format {
+            Format::Debug | Format::Markdown => match signers {
+                None => writeln!(out, "unsigned")?,
+                Some(signers) => {
+                    for signer in signers {
+                        writeln!(out, "subject: {}", signer.subject)?;
+                        writeln!(out, "issuer: {}", signer.issuer)?;
+                        writeln!(out, "serial: {}", signer.serial)?;
+                        writeln!(out, "not before: {}", signer.not_before)?;
+                        writeln!(out, "not after: {}", signer.not_after)?;
+                        writeln!(out)?;
+                    }
+                }
+            },
+            Format::Json => writeln!(out, "{}", args.to_json(&signers)?)?,
+            Format::Yaml => write!(out, "{}", to_yaml(&signers)?)?,
+            Format::Toml => {
+                // toml documents have to be a table at the top
+                let mut table = BTreeMap::new();
+                table.insert("signers", signers.unwrap_or_default());
+                write!(out, "{}", to_toml(&table)?)?
+            }
+        }
 
-    let mut flags = pkcs7::Pkcs7Flags::empty();
-    flags.insert(pkcs7::Pkcs7Flags::NOVERIFY);
+        return Result::Ok(true);
+    }
 
-    let mut xml: vec::Vec<u8> = vec::Vec::new();
+    let options = args.parse_options();
 
-    p7.verify(&stack, &store, None, Some(&mut xml), flags)
-        .expect("verify and extract pkcs7 payload");
+    if let Some(format) = &args.dump_plist {
+        let plist = extract_plist(&bytes, &options)?;
 
-    let plist = Value::from_reader(Cursor::new(xml)).expect("plist");
-    let dict = plist.as_dictionary();
+        match format {
+            None | Some(PlistFormat::Raw) => out.write_all(&plist)?,
+            Some(PlistFormat::Xml) => {
+                plist::Value::from_reader(Cursor::new(plist))?.to_writer_xml(&mut *out)?;
+                writeln!(out)?;
+            }
+        }
 
-    let contents = dict
-        .and_then(|d| d.get("PayloadContent"))
-        .and_then(|v| v.as_array())
-        .expect("array of contents");
+        return Result::Ok(true);
+    }
 
-    let (wifis, errs): (Vec<_>, Vec<_>) = contents
-        .iter()
-        .map(MobileconfWifi::parse)
-        .apply(partition_results);
-    println!("Errs: {:?}", errs);
+    if args.list_types {
+        let types = payload_types(&bytes, &options)?;
+
+        match args.format {
+            Format::Debug | Format::Markdown => {
+                for (typ, count) in &types {
+                    writeln!(out, "{}: {}", typ, count)?;
+                }
+            }
+            Format::Json => writeln!(out, "{}", args.to_json(&types)?)?,
+            Format::Yaml => write!(out, "{}", to_yaml(&types)?)?,
+            Format::Toml => write!(out, "{}", to_toml(&types)?)?,
+        }
+
+        return Result::Ok(!types.is_empty());
+    }
+
+    if let Some(index) = args.payload {
+        let payload = raw_payload(&bytes, &options, index)?;
+
+        match args.format {
+            Format::Debug | Format::Markdown => {
+                payload.to_writer_xml(&mut *out)?;
+                writeln!(out)?;
+            }
+            Format::Json => writeln!(out, "{}", args.to_json(&payload)?)?,
+            Format::Yaml => write!(out, "{}", to_yaml(&payload)?)?,
+            Format::Toml => write!(out, "{}", to_toml(&payload)?)?,
+        }
 
-    let (certs, errs): (Vec<_>, Vec<_>) = contents
-        .iter()
-        .map(MobileconfTLSCert::parse)
-        .apply(partition_results);
-    println!("Errs: {:?}", errs);
+        return Result::Ok(true);
+    }
+
+    if let Some(other) = &args.diff {
+        let other = read_input(other)?;
+        let diff = diff_profiles(&bytes, &other, &options)?;
+
+        match args.format {
+            Format::Debug | Format::Markdown => write!(out, "{}", diff)?,
+            Format::Json => writeln!(out, "{}", args.to_json(&diff)?)?,
+            Format::Yaml => write!(out, "{}", to_yaml(&diff)?)?,
+            Format::Toml => write!(out, "{}", to_toml(&diff)?)?,
+        }
+
+        return Result::Ok(true);
+    }
+
+    if args.profile_version {
+        let version = profile_metadata(&bytes, &options)?.PayloadVersion;
+        writeln!(out, "{}", version.unwrap_or(0))?;
+        return Result::Ok(version.is_some());
+    }
+
+    let mut parsed = parse_profile_with(&bytes, &options)?;
+
+    if let Some(wanted) = args.ssid_filter() {
+        if retain_wifis(&mut parsed, &|ssid| wanted.matches(ssid)) == 0 {
+            return Result::Err(MobileconfError::NoMatchingWifi(wanted.to_string()));
+        }
+    }
+
+    for warning in &parsed.warnings {
+        eprintln!("warning: {}: {}", path.display(), warning);
+    }
+
+    // the debug dump and markdown report list errors themselves, for the
+    // serialized formats they go to stderr so stdout stays a clean document
+    if !matches!(args.format, Format::Debug | Format::Markdown) {
+        report_errors(path, &parsed, args)?;
+    }
+
+    let found = !parsed.is_empty();
+    if !found {
+        eprintln!("{}: no payloads found", path.display());
+    }
 
-    println!("Found wifis: {:#?}", wifis);
-    println!("Found certs: {:#?}", certs);
+    if let Some(dir) = &args.dump_certs {
+        dump_certs(dir, &parsed.certs, args.cert_format)?;
+    }
+
+    if let Some(dir) = &args.dump_fonts {
+        dump_fonts(dir, &parsed.fonts)?;
+    }
+
+    if let Some(dir) = &args.dump_wallpaper {
+        dump_wallpapers(dir, &parsed.wallpapers)?;
+    }
+
+    if args.count {
+        let mut counts: Vec<(&str, usize)> = parsed
+            .counts()
+            .into_iter()
+            .filter(|&(kind, _, _)| options.wants(kind))
+            .map(|(_, name, count)| (name, count))
+            .collect();
+        counts.push(("errors", parsed.errors.len()));
+
+        match args.format {
+            Format::Debug | Format::Markdown => {
+                for (name, count) in &counts {
+                    writeln!(out, "{}: {}", name, count)?;
+                }
+            }
+            Format::Json => writeln!(out, "{}", args.to_json(&counts_map(&counts))?)?,
+            Format::Yaml => write!(out, "{}", to_yaml(&counts_map(&counts))?)?,
+            Format::Toml => write!(out, "{}", to_toml(&counts_map(&counts))?)?,
+        }
+
+        return Result::Ok(found);
+    }
+
+    if args.check_expiry {
+        let mut expired = 0;
+        for cert in &parsed.certs {
+            // parsing only warns about certs that don't decode, so one of
+            // them shouldn't stop the others from being checked
+            let checked = cert.to_x509().and_then(|x509| {
+                let validity = Validity::of(&x509).map_err(MobileconfError::X509)?;
+                Result::Ok((validity, x509.not_after().to_string()))
+            });
+            match checked {
+                Result::Ok((validity, not_after)) => {
+                    if validity == Validity::Expired {
+                        expired += 1;
+                    }
+                    writeln!(
+                        out,
+                        "{}: {} (not after {})",
+                        cert.PayloadUUID, validity, not_after
+                    )?;
+                }
+                Result::Err(err) => writeln!(out, "{}: error: {}", cert.PayloadUUID, err)?,
+            }
+        }
+        if expired > 0 {
+            return Result::Err(MobileconfError::Expired(expired));
+        }
+        return Result::Ok(found);
+    }
+
+    if let Some(days) = args.expiring_within {
+        let mut expiring = 0;
+        for cert in &parsed.certs {
+            let checked = cert
+                .to_x509()
+                .and_then(|x509| days_remaining(&x509).map_err(MobileconfError::X509));
+            match checked {
+                Result::Ok(remaining) if i64::from(remaining) <= i64::from(days) => {
+                    expiring += 1;
+                    // to_x509 succeeded, so the subject decodes as well
+                    writeln!(
+                        out,
+                        "{}: {} ({} days left)",
+                        cert.PayloadUUID,
+                        cert.subject().unwrap_or_default(),
+                        remaining
+                    )?;
+                }
+                Result::Ok(_) => {}
+                Result::Err(err) => writeln!(out, "{}: error: {}", cert.PayloadUUID, err)?,
+            }
+        }
+        if expiring > 0 {
+            return Result::Err(MobileconfError::Expiring(expiring, days));
+        }
+        return Result::Ok(found);
+    }
+
+    if args.qr {
+        for wifi in &parsed.wifis {
+            eprintln!(
+                "warning: skipping {}: {:?} networks can't be encoded in a WIFI: QR code",
+                wifi.SSID, wifi.EAPType
+            );
+        }
+        for wifi in &parsed.personal_wifis {
+            match wifi.to_qr_code()? {
+                Some(code) => writeln!(out, "{}\n{}", wifi.SSID, code)?,
+                None => eprintln!(
+                    "warning: skipping {}: the profile has no password for it",
+                    wifi.SSID
+                ),
+            }
+        }
+        return Result::Ok(found);
+    }
+
+    match (&args.export, &args.export_dir) {
+        (Some(Export::WpaSupplicant), _) => {
+            for wifi in &parsed.wifis {
+                writeln!(out, "{}\n", wifi.to_wpa_supplicant())?;
+            }
+            return Result::Ok(found);
+        }
+        (Some(Export::NetworkManager), Some(dir)) => {
+            write_networkmanager(dir, &parsed.wifis)?;
+            return Result::Ok(found);
+        }
+        (Some(Export::Android), _) => {
+            let mut networks = Vec::new();
+            for wifi in &parsed.wifis {
+                match wifi.to_android_network() {
+                    Some(network) => networks.push(network),
+                    None => eprintln!(
+                        "warning: skipping {}: {} is not supported on Android",
+                        wifi.SSID,
+                        match &wifi.TTLSInnerAuthentication {
+                            Some(inner) => format!("TTLS with {}", inner),
+                            None => format!("{:?}", wifi.EAPType),
+                        }
+                    ),
+                }
+            }
+            writeln!(out, "{}", android_config_store(&networks))?;
+            return Result::Ok(found);
+        }
+        (Some(Export::Keychain), _) => {
+            writeln!(
+                out,
+                "{}",
+                keychain_script(&parsed.caldavs, &parsed.carddavs)
+            )?;
+            return Result::Ok(found);
+        }
+        _ => {}
+    }
+
+    match args.format {
+        Format::Debug => {
+            let mut buf = Vec::new();
+            write_debug_dump(&mut buf, &parsed, &options, args)?;
+            write_debug(out, &buf, args.use_color())?;
+        }
+        Format::Json => {
+            let json = args.to_json(&parsed)?;
+            writeln!(out, "{}", json)?;
+        }
+        Format::Yaml => write!(out, "{}", to_yaml(&parsed)?)?,
+        Format::Toml => write!(out, "{}", to_toml(&parsed)?)?,
+        Format::Markdown => writeln!(out, "{}", parsed.to_markdown())?,
+    }
+
+    Result::Ok(found)
+}
+
+/// Nothing is logged unless asked for with `-v` or `RUST_LOG`.
+fn init_logging(verbose: u8) {
+    let mut builder =
+        env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("off"));
+    let level = match verbose {
+        0 => None,
+        1 => Some(log::LevelFilter::Info),
+        2 => Some(log::LevelFilter::Debug),
+        _ => Some(log::LevelFilter::Trace),
+    };
+    if let Some(level) = level {
+        builder.filter_level(level);
+    }
+    builder.init();
+}
+
+fn main() {
+    let args = Args::from_args();
+
+    mobileconf::set_show_secrets(args.show_secrets);
+    init_logging(args.verbose);
+
+    let mut out: Box<dyn Write> = match &args.output {
+        Some(path) => match fs::File::create(path) {
+            Result::Ok(file) => Box::new(io::BufWriter::new(file)),
+            Result::Err(err) => {
+                eprintln!("{}: {}", path.display(), err);
+                process::exit(1);
+            }
+        },
+        None => Box::new(io::stdout()),
+    };
+
+    if args.describe {
+        let result = describe(&args, &mut out).and_then(|()| out.flush().map_err(Into::into));
+        if let Result::Err(err) = result {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+        return;
+    }
+
+    // pairs of the name to report a file under and the file itself
+    let mut inputs = Vec::new();
+    for input in &args.input {
+        if args.recursive && input.is_dir() {
+            for found in find_profiles(input) {
+                let name = match &found {
+                    Result::Ok(path) => path.strip_prefix(input).unwrap_or(path).to_path_buf(),
+                    Result::Err(_) => input.clone(),
+                };
+                inputs.push((name, found));
+            }
+        } else {
+            inputs.push((input.clone(), Result::Ok(input.clone())));
+        }
+    }
+
+    let many = inputs.len() > 1;
+    let mut failed = 0;
+    let mut empty = 0;
+
+    for (name, path) in &inputs {
+        let result = match path {
+            Result::Ok(path) if many => {
+                // buffered so the output can be tagged with the file's name
+                let mut buf = Vec::new();
+                let started = Instant::now();
+                let result = run(path, &args, &mut buf);
+                info!("{}: done in {:?}", name.display(), started.elapsed());
+                if let Result::Err(err) = write_tagged(&mut out, name, &buf, &args) {
+                    eprintln!("{}: {}", name.display(), err);
+                }
+                result
+            }
+            Result::Ok(path) => {
+                let started = Instant::now();
+                let result = run(path, &args, &mut out);
+                info!("{}: done in {:?}", name.display(), started.elapsed());
+                result
+            }
+            Result::Err(err) => {
+                eprintln!("{}", err);
+                failed += 1;
+                continue;
+            }
+        };
+
+        match result {
+            Result::Ok(true) => {}
+            Result::Ok(false) => empty += 1,
+            Result::Err(err) => {
+                eprintln!("{}: {}", name.display(), err);
+                failed += 1;
+            }
+        }
+    }
+
+    if many {
+        eprintln!("{} succeeded, {} failed", inputs.len() - failed, failed);
+    }
+
+    if let Result::Err(err) = out.flush() {
+        eprintln!("{}", err);
+        process::exit(1);
+    }
+
+    if failed > 0 {
+        process::exit(1);
+    }
+    if empty > 0 {
+        process::exit(2);
+    }
 }
@@ -0,0 +1,89 @@
+use crate::dict::{as_payload, get_array_opt, get_string, get_string_opt};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use crate::Secret;
+use plist::Value;
+use serde::Serialize;
+
+/// Password to use for an AirPlay destination.
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct AirPlayPassword {
+    pub DeviceName: String,
+    pub Password: Secret,
+}
+
+impl AirPlayPassword {
+    fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = v
+            .as_dictionary()
+            .ok_or_else(|| MobileconfError::wrong_type("PasswordList", "dictionary"))?;
+
+        Result::Ok(AirPlayPassword {
+            DeviceName: get_string(dict, "DeviceName")?,
+            Password: get_string(dict, "Password").map(Secret::from)?,
+        })
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfAirPlaySecurity {
+    // device ids of the destinations that may be used
+    pub AllowList: Vec<String>,
+    pub PasswordList: Vec<AirPlayPassword>,
+}
+
+impl MobileconfAirPlaySecurity {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if !(typ == *"com.apple.airplay.security" || typ == *"com.apple.airplay") {
+                return Result::Err(MobileconfError::NotAirPlaySecurity);
+            }
+        }
+
+        // entries are either bare ids or {DeviceID = ...} dictionaries
+        let AllowList = match get_array_opt(dict, "AllowList")? {
+            Some(devices) => devices
+                .iter()
+                .map(|device| match device {
+                    Value::String(id) => Result::Ok(Some(id.clone())),
+                    Value::Dictionary(device) => get_string_opt(device, "DeviceID"),
+                    _ => Result::Err(MobileconfError::wrong_type("AllowList", "dictionary")),
+                })
+                .filter_map(Result::transpose)
+                .collect::<Result<Vec<_>, _>>()?,
+            None => Vec::new(),
+        };
+
+        // com.apple.airplay calls the list Passwords
+        let passwords = match get_array_opt(dict, "PasswordList")? {
+            Some(passwords) => Some(passwords),
+            None => get_array_opt(dict, "Passwords")?,
+        };
+
+        let PasswordList = match passwords {
+            Some(passwords) => passwords
+                .iter()
+                .map(AirPlayPassword::parse)
+                .collect::<Result<Vec<_>, _>>()?,
+            None => Vec::new(),
+        };
+
+        Result::Ok(MobileconfAirPlaySecurity {
+            AllowList,
+            PasswordList,
+        })
+    }
+}
+
+impl DescribePayload for MobileconfAirPlaySecurity {
+    const PAYLOAD_TYPES: &'static [&'static str] =
+        &["com.apple.airplay.security", "com.apple.airplay"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("AllowList", "Vec<String>"),
+        ("PasswordList", "Vec<AirPlayPassword>"),
+    ];
+}
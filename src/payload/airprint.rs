@@ -0,0 +1,74 @@
+use crate::dict::{as_payload, get_array, get_bool_opt, get_int_opt, get_string, get_string_opt};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+
+/// One printer of the `AirPrint` array.
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct AirPrintTarget {
+    pub IPAddress: String,
+    pub ResourcePath: String,
+    pub Port: Option<i64>,
+    pub ForceTLS: bool,
+}
+
+impl AirPrintTarget {
+    /// `None` for entries without an `IPAddress`.
+    fn parse(v: &Value) -> Result<Option<Self>, MobileconfError> {
+        let dict = v
+            .as_dictionary()
+            .ok_or_else(|| MobileconfError::wrong_type("AirPrint", "dictionary"))?;
+
+        let IPAddress = match get_string_opt(dict, "IPAddress")? {
+            Some(address) => address,
+            None => return Result::Ok(None),
+        };
+
+        Result::Ok(Some(AirPrintTarget {
+            IPAddress,
+            ResourcePath: get_string(dict, "ResourcePath")?,
+            Port: get_int_opt(dict, "Port")?,
+            ForceTLS: get_bool_opt(dict, "ForceTLS")?.unwrap_or(false),
+        }))
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfAirPrint {
+    pub AirPrint: Vec<AirPrintTarget>,
+    // printers left out for lacking an IPAddress
+    #[serde(skip)]
+    pub skipped: usize,
+}
+
+impl MobileconfAirPrint {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.airprint" {
+                return Result::Err(MobileconfError::NotAirPrint);
+            }
+        }
+
+        let entries = get_array(dict, "AirPrint")?;
+        let AirPrint = entries
+            .iter()
+            .map(AirPrintTarget::parse)
+            .filter_map(Result::transpose)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Result::Ok(MobileconfAirPrint {
+            skipped: entries.len() - AirPrint.len(),
+            AirPrint,
+        })
+    }
+}
+
+impl DescribePayload for MobileconfAirPrint {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.airprint"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[("AirPrint", "Vec<AirPrintTarget>")];
+}
@@ -0,0 +1,64 @@
+use crate::dict::{as_payload, get_dict, get_dict_opt, get_string};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::{Dictionary, Value};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// The entries of an options dictionary, which are all booleans.
+fn bool_options(app: &Dictionary, key: &str) -> Result<BTreeMap<String, bool>, MobileconfError> {
+    let options = match get_dict_opt(app, key)? {
+        Some(options) => options,
+        None => return Result::Ok(BTreeMap::new()),
+    };
+
+    options
+        .iter()
+        .map(|(option, value)| {
+            value
+                .as_boolean()
+                .map(|enabled| (option.clone(), enabled))
+                .ok_or_else(|| MobileconfError::wrong_type(option, "boolean"))
+        })
+        .collect()
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfAppLock {
+    // bundle identifier of the app the device is locked to, App.Identifier
+    pub Identifier: String,
+    // App.Options, e.g. disableTouch, disableVolumeButtons
+    pub Options: BTreeMap<String, bool>,
+    // App.UserEnabledOptions, what the user may toggle in accessibility
+    pub UserEnabledOptions: BTreeMap<String, bool>,
+}
+
+impl MobileconfAppLock {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.app.lock" {
+                return Result::Err(MobileconfError::NotAppLock);
+            }
+        }
+
+        let app = get_dict(dict, "App")?;
+
+        Result::Ok(MobileconfAppLock {
+            Identifier: get_string(app, "Identifier")?,
+            Options: bool_options(app, "Options")?,
+            UserEnabledOptions: bool_options(app, "UserEnabledOptions")?,
+        })
+    }
+}
+
+impl DescribePayload for MobileconfAppLock {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.app.lock"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("Identifier", "String"),
+        ("Options", "BTreeMap<String, bool>"),
+        ("UserEnabledOptions", "BTreeMap<String, bool>"),
+    ];
+}
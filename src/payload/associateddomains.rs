@@ -0,0 +1,62 @@
+use crate::dict::{as_payload, get_array_opt, get_string};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfAssociatedDomains {
+    // domains keyed by their service prefix, `webcredentials:example.com`
+    // ends up as webcredentials => [example.com]
+    pub AssociatedDomains: BTreeMap<String, Vec<String>>,
+}
+
+impl MobileconfAssociatedDomains {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.associated-domains" {
+                return Result::Err(MobileconfError::NotAssociatedDomains);
+            }
+        }
+
+        // domains are either listed directly or per app in Configuration
+        let mut domains = Vec::new();
+        for key in &["associatedDomains", "AssociatedDomains"] {
+            domains.extend(get_array_opt(dict, key)?.into_iter().flatten());
+        }
+        for app in get_array_opt(dict, "Configuration")?.into_iter().flatten() {
+            let app = app
+                .as_dictionary()
+                .ok_or_else(|| MobileconfError::wrong_type("Configuration", "dictionary"))?;
+            domains.extend(
+                get_array_opt(app, "AssociatedDomains")?
+                    .into_iter()
+                    .flatten(),
+            );
+        }
+
+        let mut AssociatedDomains = BTreeMap::new();
+        for domain in domains.into_iter().filter_map(Value::as_string) {
+            let (service, domain) = match domain.split_once(':') {
+                Some((service, domain)) => (service, domain),
+                None => ("(none)", domain),
+            };
+            AssociatedDomains
+                .entry(service.to_string())
+                .or_insert_with(Vec::new)
+                .push(domain.to_string());
+        }
+
+        Result::Ok(MobileconfAssociatedDomains { AssociatedDomains })
+    }
+}
+
+impl DescribePayload for MobileconfAssociatedDomains {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.associated-domains"];
+    const FIELDS: &'static [(&'static str, &'static str)] =
+        &[("AssociatedDomains", "BTreeMap<String, Vec<String>>")];
+}
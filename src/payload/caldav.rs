@@ -0,0 +1,67 @@
+use crate::dict::{as_payload, get_bool_opt, get_int_opt, get_string, get_string_opt};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use crate::Secret;
+use plist::{Dictionary, Value};
+use serde::Serialize;
+
+/// Port and SSL setting of a CalDAV or CardDAV account. Left out keys mean
+/// https on the standard port.
+pub(crate) fn dav_connection(
+    dict: &Dictionary,
+    port_key: &str,
+    ssl_key: &str,
+) -> Result<(i64, bool), MobileconfError> {
+    let port = get_int_opt(dict, port_key)?.unwrap_or(443);
+    let ssl = get_bool_opt(dict, ssl_key)?.unwrap_or(true);
+    Result::Ok((port, ssl))
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfCalDAV {
+    pub CalDAVAccountDescription: Option<String>,
+    pub CalDAVHostName: String,
+    pub CalDAVUsername: Option<String>,
+    pub CalDAVPassword: Option<Secret>,
+    pub CalDAVPort: i64,
+    pub CalDAVUseSSL: bool,
+    pub CalDAVPrincipalURL: Option<String>,
+}
+
+impl MobileconfCalDAV {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.caldav.account" {
+                return Result::Err(MobileconfError::NotCalDAV);
+            }
+        }
+
+        let (port, ssl) = dav_connection(dict, "CalDAVPort", "CalDAVUseSSL")?;
+
+        Result::Ok(MobileconfCalDAV {
+            CalDAVAccountDescription: get_string_opt(dict, "CalDAVAccountDescription")?,
+            CalDAVHostName: get_string(dict, "CalDAVHostName")?,
+            CalDAVUsername: get_string_opt(dict, "CalDAVUsername")?,
+            CalDAVPassword: get_string_opt(dict, "CalDAVPassword")?.map(Secret::from),
+            CalDAVPort: port,
+            CalDAVUseSSL: ssl,
+            CalDAVPrincipalURL: get_string_opt(dict, "CalDAVPrincipalURL")?,
+        })
+    }
+}
+
+impl DescribePayload for MobileconfCalDAV {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.caldav.account"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("CalDAVAccountDescription", "Option<String>"),
+        ("CalDAVHostName", "String"),
+        ("CalDAVUsername", "Option<String>"),
+        ("CalDAVPassword", "Option<Secret>"),
+        ("CalDAVPort", "i64"),
+        ("CalDAVUseSSL", "bool"),
+        ("CalDAVPrincipalURL", "Option<String>"),
+    ];
+}
@@ -0,0 +1,53 @@
+use super::caldav::dav_connection;
+use crate::dict::{as_payload, get_string, get_string_opt};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use crate::Secret;
+use plist::Value;
+use serde::Serialize;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfCardDAV {
+    pub CardDAVAccountDescription: Option<String>,
+    pub CardDAVHostName: String,
+    pub CardDAVUsername: Option<String>,
+    pub CardDAVPassword: Option<Secret>,
+    pub CardDAVPort: i64,
+    pub CardDAVUseSSL: bool,
+}
+
+impl MobileconfCardDAV {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.carddav.account" {
+                return Result::Err(MobileconfError::NotCardDAV);
+            }
+        }
+
+        let (port, ssl) = dav_connection(dict, "CardDAVPort", "CardDAVUseSSL")?;
+
+        Result::Ok(MobileconfCardDAV {
+            CardDAVAccountDescription: get_string_opt(dict, "CardDAVAccountDescription")?,
+            CardDAVHostName: get_string(dict, "CardDAVHostName")?,
+            CardDAVUsername: get_string_opt(dict, "CardDAVUsername")?,
+            CardDAVPassword: get_string_opt(dict, "CardDAVPassword")?.map(Secret::from),
+            CardDAVPort: port,
+            CardDAVUseSSL: ssl,
+        })
+    }
+}
+
+impl DescribePayload for MobileconfCardDAV {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.carddav.account"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("CardDAVAccountDescription", "Option<String>"),
+        ("CardDAVHostName", "String"),
+        ("CardDAVUsername", "Option<String>"),
+        ("CardDAVPassword", "Option<Secret>"),
+        ("CardDAVPort", "i64"),
+        ("CardDAVUseSSL", "bool"),
+    ];
+}
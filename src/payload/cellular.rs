@@ -0,0 +1,100 @@
+use crate::dict::{as_payload, get_array_opt, get_string, get_string_opt};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use crate::Secret;
+use plist::Value;
+use serde::Serialize;
+
+/// An access point, either the `AttachAPN` or one of `APNs`.
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct CellularAPN {
+    pub Name: String,
+    // CHAP or PAP
+    pub AuthenticationType: Option<String>,
+    pub Username: Option<String>,
+    pub Password: Option<Secret>,
+}
+
+impl CellularAPN {
+    fn parse(key: &str, v: &Value) -> Result<Self, MobileconfError> {
+        let dict = v
+            .as_dictionary()
+            .ok_or_else(|| MobileconfError::wrong_type(key, "dictionary"))?;
+
+        Result::Ok(CellularAPN {
+            Name: get_string(dict, "Name")?,
+            AuthenticationType: get_string_opt(dict, "AuthenticationType")?,
+            Username: get_string_opt(dict, "Username")?,
+            Password: get_string_opt(dict, "Password")?.map(Secret::from),
+        })
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfCellular {
+    pub AttachAPN: Option<CellularAPN>,
+    pub APNs: Vec<CellularAPN>,
+    // eSIM activation: the carrier's SM-DP+ server and what to ask it for
+    pub ServerURL: Option<String>,
+    pub MatchingID: Option<String>,
+    pub OID: Option<String>,
+}
+
+impl MobileconfCellular {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.cellular" {
+                return Result::Err(MobileconfError::NotCellular);
+            }
+        }
+
+        let AttachAPN = match dict.get("AttachAPN") {
+            Some(apn) => Some(CellularAPN::parse("AttachAPN", apn)?),
+            None => None,
+        };
+
+        let APNs = match get_array_opt(dict, "APNs")? {
+            Some(apns) => apns
+                .iter()
+                .map(|apn| CellularAPN::parse("APNs", apn))
+                .collect::<Result<Vec<_>, _>>()?,
+            None => Vec::new(),
+        };
+
+        Result::Ok(MobileconfCellular {
+            AttachAPN,
+            APNs,
+            ServerURL: get_string_opt(dict, "ServerURL")?,
+            MatchingID: get_string_opt(dict, "MatchingID")?,
+            OID: get_string_opt(dict, "OID")?,
+        })
+    }
+
+    /// The eSIM activation keys present in the payload. They're printed like
+    /// any other field, but anyone holding them can provision the eSIM.
+    pub fn carrier_sensitive(&self) -> Vec<&'static str> {
+        let mut keys = Vec::new();
+        if self.MatchingID.is_some() {
+            keys.push("MatchingID");
+        }
+        if self.OID.is_some() {
+            keys.push("OID");
+        }
+        keys
+    }
+}
+
+impl DescribePayload for MobileconfCellular {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.cellular"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("AttachAPN", "Option<CellularAPN>"),
+        ("APNs", "Vec<CellularAPN>"),
+        ("ServerURL", "Option<String>"),
+        ("MatchingID", "Option<String>"),
+        ("OID", "Option<String>"),
+    ];
+}
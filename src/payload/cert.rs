@@ -0,0 +1,148 @@
+use crate::certinfo::format_name;
+use crate::dict::{as_payload, get_string, get_value};
+use crate::files::payload_file;
+use crate::DescribePayload;
+use crate::MobileconfError;
+use openssl::x509::X509;
+use plist::Value;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfTLSCert {
+    pub PayloadUUID: String,
+    // tls cert bytes
+    pub PayloadContent: String,
+}
+
+impl MobileconfTLSCert {
+    #[allow(non_snake_case)]
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if !(typ == *"com.apple.security.pem" || typ == *"com.apple.security.root") {
+                return Result::Err(MobileconfError::NotCertificate);
+            }
+        }
+
+        let PayloadUUID = get_string(dict, "PayloadUUID")?;
+
+        let data: &[u8] = get_value(dict, "PayloadContent")?
+            .as_data()
+            .ok_or_else(|| MobileconfError::wrong_type("PayloadContent", "data"))?;
+
+        let PayloadContent = base64::encode(data);
+
+        Result::Ok(MobileconfTLSCert {
+            PayloadUUID,
+            PayloadContent,
+        })
+    }
+
+    /// PEM encoding of the certificate. `com.apple.security.pem` payloads may
+    /// already be PEM, those are passed through as is.
+    pub fn to_pem(&self) -> Result<String, MobileconfError> {
+        let data = base64::decode(&self.PayloadContent)?;
+
+        if data.starts_with(b"-----BEGIN") {
+            return Result::Ok(String::from_utf8_lossy(&data).into_owned());
+        }
+
+        let mut pem = String::from("-----BEGIN CERTIFICATE-----\n");
+        for line in self.PayloadContent.as_bytes().chunks(64) {
+            // base64 output is always ascii
+            pem.push_str(&String::from_utf8_lossy(line));
+            pem.push('\n');
+        }
+        pem.push_str("-----END CERTIFICATE-----\n");
+
+        Result::Ok(pem)
+    }
+
+    /// DER encoding of the certificate. DER payloads are returned untouched.
+    pub fn to_der(&self) -> Result<Vec<u8>, MobileconfError> {
+        let data = base64::decode(&self.PayloadContent)?;
+
+        if data.starts_with(b"-----BEGIN") {
+            return self.to_x509()?.to_der().map_err(MobileconfError::X509);
+        }
+
+        Result::Ok(data)
+    }
+
+    /// Decode the certificate, whether it was embedded as DER or PEM.
+    pub fn to_x509(&self) -> Result<X509, MobileconfError> {
+        let data = base64::decode(&self.PayloadContent)?;
+
+        if data.starts_with(b"-----BEGIN") {
+            X509::from_pem(&data)
+        } else {
+            X509::from_der(&data)
+        }
+        .map_err(MobileconfError::X509)
+    }
+
+    /// Subject of the certificate, formatted like `CN=example, O=Org`.
+    pub fn subject(&self) -> Result<String, MobileconfError> {
+        Result::Ok(format_name(self.to_x509()?.subject_name()))
+    }
+}
+
+/// Encodings `dump_certs` can write.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CertFormat {
+    Pem,
+    Der,
+}
+
+impl FromStr for CertFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pem" => Result::Ok(CertFormat::Pem),
+            "der" => Result::Ok(CertFormat::Der),
+            _ => Result::Err(format!("unknown certificate format: {}", s)),
+        }
+    }
+}
+
+/// Write each certificate to `dir/<PayloadUUID>.pem` or `.der`, creating `dir`
+/// if needed. Path separators in the UUID are replaced, so every file lands in
+/// `dir`.
+pub fn dump_certs(
+    dir: &Path,
+    certs: &[MobileconfTLSCert],
+    format: CertFormat,
+) -> Result<(), MobileconfError> {
+    fs::create_dir_all(dir).map_err(|source| MobileconfError::Write {
+        path: dir.to_path_buf(),
+        source,
+    })?;
+
+    for cert in certs {
+        let (extension, contents) = match format {
+            CertFormat::Pem => ("pem", cert.to_pem()?.into_bytes()),
+            CertFormat::Der => ("der", cert.to_der()?),
+        };
+
+        let path = payload_file(dir, &cert.PayloadUUID, extension);
+        fs::write(&path, contents).map_err(|source| MobileconfError::Write {
+            path: path.clone(),
+            source,
+        })?;
+    }
+
+    Result::Ok(())
+}
+
+impl DescribePayload for MobileconfTLSCert {
+    const PAYLOAD_TYPES: &'static [&'static str] =
+        &["com.apple.security.pem", "com.apple.security.root"];
+    const FIELDS: &'static [(&'static str, &'static str)] =
+        &[("PayloadUUID", "String"), ("PayloadContent", "String")];
+}
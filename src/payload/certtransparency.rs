@@ -0,0 +1,52 @@
+use crate::dict::{as_payload, get_array_opt, get_bool_opt, get_string};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfCertTransparency {
+    // domains certificate transparency is not enforced for
+    pub DisableForDomains: Vec<String>,
+}
+
+impl MobileconfCertTransparency {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.security.certificatetransparency" {
+                return Result::Err(MobileconfError::NotCertTransparency);
+            }
+        }
+
+        // entries are either bare domains or {Domain = ...; DisableCT = ...}
+        // dictionaries, which only count when DisableCT isn't false
+        let DisableForDomains = match get_array_opt(dict, "DisableForDomains")? {
+            Some(domains) => domains
+                .iter()
+                .map(|domain| match domain {
+                    Value::String(domain) => Result::Ok(Some(domain.clone())),
+                    Value::Dictionary(entry) => {
+                        if get_bool_opt(entry, "DisableCT")?.unwrap_or(true) {
+                            get_string(entry, "Domain").map(Some)
+                        } else {
+                            Result::Ok(None)
+                        }
+                    }
+                    _ => Result::Err(MobileconfError::wrong_type("DisableForDomains", "string")),
+                })
+                .filter_map(Result::transpose)
+                .collect::<Result<Vec<_>, _>>()?,
+            None => Vec::new(),
+        };
+
+        Result::Ok(MobileconfCertTransparency { DisableForDomains })
+    }
+}
+
+impl DescribePayload for MobileconfCertTransparency {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.security.certificatetransparency"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[("DisableForDomains", "Vec<String>")];
+}
@@ -0,0 +1,139 @@
+use crate::dict::{
+    as_payload, get_array_opt, get_bool_opt, get_string, get_string_opt, get_strings_opt,
+};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// An entry of `WhitelistedBookmarks`.
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct Bookmark {
+    pub URL: String,
+    pub Title: Option<String>,
+}
+
+impl Bookmark {
+    fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = v
+            .as_dictionary()
+            .ok_or_else(|| MobileconfError::wrong_type("WhitelistedBookmarks", "dictionary"))?;
+
+        Result::Ok(Bookmark {
+            URL: get_string(dict, "URL")?,
+            Title: get_string_opt(dict, "Title")?,
+        })
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfContentFilter {
+    // BuiltIn or Plugin
+    pub FilterType: String,
+    pub UserDefinedName: Option<String>,
+    // only for Plugin filters
+    pub PluginBundleID: Option<String>,
+    pub FilterBrowsers: bool,
+    pub FilterSockets: bool,
+    pub WhitelistedBookmarks: Vec<Bookmark>,
+    pub PermittedURLs: Vec<String>,
+    pub BlacklistedURLs: Vec<String>,
+    // the bookmarks and permitted urls, normalized, sorted and deduplicated
+    pub allowed_urls: Vec<String>,
+    // BlacklistedURLs, normalized, sorted and deduplicated
+    pub blocked_urls: Vec<String>,
+}
+
+/// Lowercase the host and drop a trailing slash from the path, so
+/// `https://Example.com/` and `https://example.com` compare equal. Paths are
+/// case sensitive and, like the query and fragment, are left alone.
+fn normalize_url(url: &str) -> String {
+    let url = url.trim();
+    let (scheme, rest) = match url.find("://") {
+        Some(at) => url.split_at(at + 3),
+        None => ("", url),
+    };
+    let host_end = rest
+        .find(|c| c == '/' || c == '?' || c == '#')
+        .unwrap_or(rest.len());
+    let (host, rest) = rest.split_at(host_end);
+    let path_end = rest.find(|c| c == '?' || c == '#').unwrap_or(rest.len());
+    let (path, query) = rest.split_at(path_end);
+
+    let normalized = format!("{}{}{}", scheme.to_lowercase(), host.to_lowercase(), path);
+    format!("{}{}", normalized.trim_end_matches('/'), query)
+}
+
+fn normalize_urls<'a, I>(urls: I) -> Vec<String>
+where
+    I: Iterator<Item = &'a str>,
+{
+    urls.map(normalize_url)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+impl MobileconfContentFilter {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.webcontent-filter" {
+                return Result::Err(MobileconfError::NotContentFilter);
+            }
+        }
+
+        let WhitelistedBookmarks = match get_array_opt(dict, "WhitelistedBookmarks")? {
+            Some(bookmarks) => bookmarks
+                .iter()
+                .map(Bookmark::parse)
+                .collect::<Result<Vec<_>, _>>()?,
+            None => Vec::new(),
+        };
+
+        let PermittedURLs = get_strings_opt(dict, "PermittedURLs")?.unwrap_or_default();
+        let BlacklistedURLs = get_strings_opt(dict, "BlacklistedURLs")?.unwrap_or_default();
+
+        let allowed_urls = normalize_urls(
+            WhitelistedBookmarks
+                .iter()
+                .map(|bookmark| bookmark.URL.as_str())
+                .chain(PermittedURLs.iter().map(String::as_str)),
+        );
+        let blocked_urls = normalize_urls(BlacklistedURLs.iter().map(String::as_str));
+
+        Result::Ok(MobileconfContentFilter {
+            FilterType: get_string_opt(dict, "FilterType")?
+                .unwrap_or_else(|| "BuiltIn".to_string()),
+            UserDefinedName: get_string_opt(dict, "UserDefinedName")?,
+            PluginBundleID: get_string_opt(dict, "PluginBundleID")?,
+            FilterBrowsers: get_bool_opt(dict, "FilterBrowsers")?.unwrap_or(false),
+            FilterSockets: get_bool_opt(dict, "FilterSockets")?.unwrap_or(false),
+            WhitelistedBookmarks,
+            PermittedURLs,
+            BlacklistedURLs,
+            allowed_urls,
+            blocked_urls,
+        })
+    }
+}
+
+impl DescribePayload for MobileconfContentFilter {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.webcontent-filter"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("FilterType", "String"),
+        ("UserDefinedName", "Option<String>"),
+        ("PluginBundleID", "Option<String>"),
+        ("FilterBrowsers", "bool"),
+        ("FilterSockets", "bool"),
+        ("WhitelistedBookmarks", "Vec<Bookmark>"),
+        ("PermittedURLs", "Vec<String>"),
+        ("BlacklistedURLs", "Vec<String>"),
+        ("allowed_urls", "Vec<String>"),
+        ("blocked_urls", "Vec<String>"),
+    ];
+}
@@ -0,0 +1,51 @@
+use crate::dict::{as_payload, get_dict, get_string, get_string_opt, get_strings_opt};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+
+/// Encrypted DNS settings, taken from the nested `DNSSettings` dictionary.
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfDNS {
+    // HTTPS or TLS
+    pub DNSProtocol: String,
+    // set for DNS over HTTPS
+    pub ServerURL: Option<String>,
+    // set for DNS over TLS
+    pub ServerName: Option<String>,
+    pub ServerAddresses: Vec<String>,
+}
+
+impl MobileconfDNS {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.dnsSettings.managed" {
+                return Result::Err(MobileconfError::NotDNS);
+            }
+        }
+
+        let settings = get_dict(dict, "DNSSettings")?;
+
+        let ServerAddresses = get_strings_opt(settings, "ServerAddresses")?.unwrap_or_default();
+
+        Result::Ok(MobileconfDNS {
+            DNSProtocol: get_string(settings, "DNSProtocol")?,
+            ServerURL: get_string_opt(settings, "ServerURL")?,
+            ServerName: get_string_opt(settings, "ServerName")?,
+            ServerAddresses,
+        })
+    }
+}
+
+impl DescribePayload for MobileconfDNS {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.dnsSettings.managed"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("DNSProtocol", "String"),
+        ("ServerURL", "Option<String>"),
+        ("ServerName", "Option<String>"),
+        ("ServerAddresses", "Vec<String>"),
+    ];
+}
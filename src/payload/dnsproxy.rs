@@ -0,0 +1,55 @@
+use crate::dict::{as_payload, get_string, get_string_opt};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+
+/// A DNS proxy network extension, not to be confused with
+/// [`MobileconfDNS`].
+///
+/// [`MobileconfDNS`]: crate::MobileconfDNS
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfDNSProxy {
+    pub AppBundleIdentifier: String,
+    pub ProviderBundleIdentifier: Option<String>,
+    // provider specific, so kept untyped
+    pub ProviderConfiguration: Option<serde_json::Value>,
+}
+
+impl MobileconfDNSProxy {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.dnsProxy.managed" {
+                return Result::Err(MobileconfError::NotDNSProxy);
+            }
+        }
+
+        let ProviderConfiguration = match dict.get("ProviderConfiguration") {
+            Some(config) => {
+                config.as_dictionary().ok_or_else(|| {
+                    MobileconfError::wrong_type("ProviderConfiguration", "dictionary")
+                })?;
+                Some(serde_json::to_value(config)?)
+            }
+            None => None,
+        };
+
+        Result::Ok(MobileconfDNSProxy {
+            AppBundleIdentifier: get_string(dict, "AppBundleIdentifier")?,
+            ProviderBundleIdentifier: get_string_opt(dict, "ProviderBundleIdentifier")?,
+            ProviderConfiguration,
+        })
+    }
+}
+
+impl DescribePayload for MobileconfDNSProxy {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.dnsProxy.managed"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("AppBundleIdentifier", "String"),
+        ("ProviderBundleIdentifier", "Option<String>"),
+        ("ProviderConfiguration", "Option<serde_json::Value>"),
+    ];
+}
@@ -0,0 +1,55 @@
+use crate::dict::{as_payload, get_int_opt, get_string, get_string_opt};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfEmail {
+    pub EmailAccountName: Option<String>,
+    pub EmailAddress: Option<String>,
+    pub IncomingMailServerHostName: String,
+    pub IncomingMailServerPortNumber: Option<i64>,
+    pub IncomingMailServerUsername: Option<String>,
+    pub OutgoingMailServerHostName: String,
+    pub OutgoingMailServerPortNumber: Option<i64>,
+    pub OutgoingMailServerUsername: Option<String>,
+}
+
+impl MobileconfEmail {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.mail.managed" {
+                return Result::Err(MobileconfError::NotEmail);
+            }
+        }
+
+        Result::Ok(MobileconfEmail {
+            EmailAccountName: get_string_opt(dict, "EmailAccountName")?,
+            EmailAddress: get_string_opt(dict, "EmailAddress")?,
+            IncomingMailServerHostName: get_string(dict, "IncomingMailServerHostName")?,
+            IncomingMailServerPortNumber: get_int_opt(dict, "IncomingMailServerPortNumber")?,
+            IncomingMailServerUsername: get_string_opt(dict, "IncomingMailServerUsername")?,
+            OutgoingMailServerHostName: get_string(dict, "OutgoingMailServerHostName")?,
+            OutgoingMailServerPortNumber: get_int_opt(dict, "OutgoingMailServerPortNumber")?,
+            OutgoingMailServerUsername: get_string_opt(dict, "OutgoingMailServerUsername")?,
+        })
+    }
+}
+
+impl DescribePayload for MobileconfEmail {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.mail.managed"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("EmailAccountName", "Option<String>"),
+        ("EmailAddress", "Option<String>"),
+        ("IncomingMailServerHostName", "String"),
+        ("IncomingMailServerPortNumber", "Option<i64>"),
+        ("IncomingMailServerUsername", "Option<String>"),
+        ("OutgoingMailServerHostName", "String"),
+        ("OutgoingMailServerPortNumber", "Option<i64>"),
+        ("OutgoingMailServerUsername", "Option<String>"),
+    ];
+}
@@ -0,0 +1,47 @@
+use crate::dict::{as_payload, get_bool_opt, get_string, get_string_opt};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfExchange {
+    pub EmailAddress: Option<String>,
+    pub Host: String,
+    pub UserName: Option<String>,
+    pub SSL: bool,
+    pub Domain: Option<String>,
+}
+
+impl MobileconfExchange {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.eas.account" {
+                return Result::Err(MobileconfError::NotExchange);
+            }
+        }
+
+        Result::Ok(MobileconfExchange {
+            EmailAddress: get_string_opt(dict, "EmailAddress")?,
+            Host: get_string(dict, "Host")?,
+            UserName: get_string_opt(dict, "UserName")?,
+            // Apple defaults to SSL when the key is left out
+            SSL: get_bool_opt(dict, "SSL")?.unwrap_or(true),
+            Domain: get_string_opt(dict, "Domain")?,
+        })
+    }
+}
+
+impl DescribePayload for MobileconfExchange {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.eas.account"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("EmailAddress", "Option<String>"),
+        ("Host", "String"),
+        ("UserName", "Option<String>"),
+        ("SSL", "bool"),
+        ("Domain", "Option<String>"),
+    ];
+}
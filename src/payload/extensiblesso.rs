@@ -0,0 +1,45 @@
+use crate::dict::{as_payload, get_string, get_strings_opt};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfExtensibleSSO {
+    pub ExtensionIdentifier: String,
+    // Credential or Redirect
+    pub Type: String,
+    // Credential extensions match on hosts, Redirect ones on URLs
+    pub Hosts: Vec<String>,
+    pub URLs: Vec<String>,
+}
+
+impl MobileconfExtensibleSSO {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.extensiblesso" {
+                return Result::Err(MobileconfError::NotExtensibleSSO);
+            }
+        }
+
+        Result::Ok(MobileconfExtensibleSSO {
+            ExtensionIdentifier: get_string(dict, "ExtensionIdentifier")?,
+            Type: get_string(dict, "Type")?,
+            Hosts: get_strings_opt(dict, "Hosts")?.unwrap_or_default(),
+            URLs: get_strings_opt(dict, "URLs")?.unwrap_or_default(),
+        })
+    }
+}
+
+impl DescribePayload for MobileconfExtensibleSSO {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.extensiblesso"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("ExtensionIdentifier", "String"),
+        ("Type", "String"),
+        ("Hosts", "Vec<String>"),
+        ("URLs", "Vec<String>"),
+    ];
+}
@@ -0,0 +1,81 @@
+use crate::dict::{as_payload, get_string, get_string_opt, get_value};
+use crate::files::payload_file;
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfFont {
+    pub PayloadUUID: String,
+    pub Name: Option<String>,
+    // font file bytes
+    pub Font: String,
+}
+
+impl MobileconfFont {
+    #[allow(non_snake_case)]
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.font" {
+                return Result::Err(MobileconfError::NotFont);
+            }
+        }
+
+        let data: &[u8] = get_value(dict, "Font")?
+            .as_data()
+            .ok_or_else(|| MobileconfError::wrong_type("Font", "data"))?;
+
+        Result::Ok(MobileconfFont {
+            PayloadUUID: get_string(dict, "PayloadUUID")?,
+            Name: get_string_opt(dict, "Name")?,
+            Font: base64::encode(data),
+        })
+    }
+
+    /// File extension for the font, going by its magic bytes.
+    pub fn extension(&self) -> Result<&'static str, MobileconfError> {
+        let data = base64::decode(&self.Font)?;
+
+        let extension = match data.get(..4) {
+            Some(b"OTTO") => "otf",
+            Some(b"\x00\x01\x00\x00") | Some(b"true") => "ttf",
+            Some(b"ttcf") => "ttc",
+            _ => "bin",
+        };
+
+        Result::Ok(extension)
+    }
+}
+
+/// Write each font to `dir/<PayloadUUID>.<ext>`, creating `dir` if needed.
+pub fn dump_fonts(dir: &Path, fonts: &[MobileconfFont]) -> Result<(), MobileconfError> {
+    fs::create_dir_all(dir).map_err(|source| MobileconfError::Write {
+        path: dir.to_path_buf(),
+        source,
+    })?;
+
+    for font in fonts {
+        let path = payload_file(dir, &font.PayloadUUID, font.extension()?);
+        fs::write(&path, base64::decode(&font.Font)?).map_err(|source| MobileconfError::Write {
+            path: path.clone(),
+            source,
+        })?;
+    }
+
+    Result::Ok(())
+}
+
+impl DescribePayload for MobileconfFont {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.font"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("PayloadUUID", "String"),
+        ("Name", "Option<String>"),
+        ("Font", "String"),
+    ];
+}
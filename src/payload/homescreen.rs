@@ -0,0 +1,139 @@
+use crate::dict::{as_payload, get_array_opt, get_string, get_string_opt};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+use std::fmt;
+
+/// One icon of a home screen page or the dock.
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+#[serde(tag = "Type")]
+pub enum HomeScreenItem {
+    Application {
+        BundleID: String,
+    },
+    Folder {
+        DisplayName: Option<String>,
+        Pages: Vec<Vec<HomeScreenItem>>,
+    },
+    WebClip {
+        URL: String,
+    },
+}
+
+impl HomeScreenItem {
+    fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = v
+            .as_dictionary()
+            .ok_or_else(|| MobileconfError::wrong_type("Pages", "dictionary"))?;
+
+        match get_string(dict, "Type")?.as_str() {
+            "Application" => Result::Ok(HomeScreenItem::Application {
+                BundleID: get_string(dict, "BundleID")?,
+            }),
+            "Folder" => Result::Ok(HomeScreenItem::Folder {
+                DisplayName: get_string_opt(dict, "DisplayName")?,
+                Pages: parse_pages(get_array_opt(dict, "Pages")?)?,
+            }),
+            "WebClip" => Result::Ok(HomeScreenItem::WebClip {
+                URL: get_string(dict, "URL")?,
+            }),
+            _ => Result::Err(MobileconfError::wrong_type(
+                "Type",
+                "Application, Folder or WebClip",
+            )),
+        }
+    }
+
+    fn write_tree(&self, f: &mut fmt::Formatter, depth: usize) -> fmt::Result {
+        let indent = "  ".repeat(depth);
+        match self {
+            HomeScreenItem::Application { BundleID } => writeln!(f, "{}{}", indent, BundleID),
+            HomeScreenItem::WebClip { URL } => writeln!(f, "{}web clip {}", indent, URL),
+            HomeScreenItem::Folder { DisplayName, Pages } => {
+                writeln!(
+                    f,
+                    "{}folder {}",
+                    indent,
+                    DisplayName.as_deref().unwrap_or("(unnamed)")
+                )?;
+                write_pages(f, Pages, depth + 1)
+            }
+        }
+    }
+}
+
+fn parse_items(items: &[Value]) -> Result<Vec<HomeScreenItem>, MobileconfError> {
+    items.iter().map(HomeScreenItem::parse).collect()
+}
+
+fn parse_pages(pages: Option<&Vec<Value>>) -> Result<Vec<Vec<HomeScreenItem>>, MobileconfError> {
+    pages
+        .into_iter()
+        .flatten()
+        .map(|page| {
+            page.as_array()
+                .ok_or_else(|| MobileconfError::wrong_type("Pages", "array"))
+                .and_then(|items| parse_items(items))
+        })
+        .collect()
+}
+
+fn write_pages(f: &mut fmt::Formatter, pages: &[Vec<HomeScreenItem>], depth: usize) -> fmt::Result {
+    for (number, page) in pages.iter().enumerate() {
+        writeln!(f, "{}page {}", "  ".repeat(depth), number + 1)?;
+        for item in page {
+            item.write_tree(f, depth + 1)?;
+        }
+    }
+    Result::Ok(())
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfHomeScreen {
+    pub Dock: Vec<HomeScreenItem>,
+    pub Pages: Vec<Vec<HomeScreenItem>>,
+}
+
+impl MobileconfHomeScreen {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.homescreenlayout" {
+                return Result::Err(MobileconfError::NotHomeScreen);
+            }
+        }
+
+        let Dock = match get_array_opt(dict, "Dock")? {
+            Some(dock) => parse_items(dock)?,
+            None => Vec::new(),
+        };
+
+        Result::Ok(MobileconfHomeScreen {
+            Dock,
+            Pages: parse_pages(get_array_opt(dict, "Pages")?)?,
+        })
+    }
+}
+
+/// The layout as an indented tree of the dock and each page.
+impl fmt::Display for MobileconfHomeScreen {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "dock")?;
+        for item in &self.Dock {
+            item.write_tree(f, 1)?;
+        }
+        write_pages(f, &self.Pages, 0)
+    }
+}
+
+impl DescribePayload for MobileconfHomeScreen {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.homescreenlayout"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("Dock", "Vec<HomeScreenItem>"),
+        ("Pages", "Vec<Vec<HomeScreenItem>>"),
+    ];
+}
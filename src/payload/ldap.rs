@@ -0,0 +1,78 @@
+use crate::dict::{as_payload, get_array_opt, get_bool_opt, get_string, get_string_opt};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+
+/// One entry of `LDAPSearchSettings`.
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct LdapSearch {
+    pub LDAPSearchSettingDescription: Option<String>,
+    pub LDAPSearchSettingSearchBase: String,
+    // LDAPSearchSettingScopeBase, ...OneLevel or ...Subtree
+    pub LDAPSearchSettingScope: Option<String>,
+}
+
+impl LdapSearch {
+    fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = v
+            .as_dictionary()
+            .ok_or_else(|| MobileconfError::wrong_type("LDAPSearchSettings", "dictionary"))?;
+
+        Result::Ok(LdapSearch {
+            LDAPSearchSettingDescription: get_string_opt(dict, "LDAPSearchSettingDescription")?,
+            LDAPSearchSettingSearchBase: get_string(dict, "LDAPSearchSettingSearchBase")?,
+            LDAPSearchSettingScope: get_string_opt(dict, "LDAPSearchSettingScope")?,
+        })
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfLDAP {
+    pub LDAPAccountDescription: Option<String>,
+    pub LDAPAccountHostName: String,
+    pub LDAPAccountUseSSL: bool,
+    pub LDAPAccountUserName: Option<String>,
+    pub LDAPSearchSettings: Vec<LdapSearch>,
+}
+
+impl MobileconfLDAP {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.ldap.account" {
+                return Result::Err(MobileconfError::NotLDAP);
+            }
+        }
+
+        let LDAPSearchSettings = match get_array_opt(dict, "LDAPSearchSettings")? {
+            Some(settings) => settings
+                .iter()
+                .map(LdapSearch::parse)
+                .collect::<Result<Vec<_>, _>>()?,
+            None => Vec::new(),
+        };
+
+        Result::Ok(MobileconfLDAP {
+            LDAPAccountDescription: get_string_opt(dict, "LDAPAccountDescription")?,
+            LDAPAccountHostName: get_string(dict, "LDAPAccountHostName")?,
+            LDAPAccountUseSSL: get_bool_opt(dict, "LDAPAccountUseSSL")?.unwrap_or(false),
+            LDAPAccountUserName: get_string_opt(dict, "LDAPAccountUserName")?,
+            LDAPSearchSettings,
+        })
+    }
+}
+
+impl DescribePayload for MobileconfLDAP {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.ldap.account"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("LDAPAccountDescription", "Option<String>"),
+        ("LDAPAccountHostName", "String"),
+        ("LDAPAccountUseSSL", "bool"),
+        ("LDAPAccountUserName", "Option<String>"),
+        ("LDAPSearchSettings", "Vec<LdapSearch>"),
+    ];
+}
@@ -0,0 +1,42 @@
+use crate::dict::{as_payload, get_string, get_string_opt};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+
+/// Device information shown on the lock screen and login window.
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfLockScreen {
+    pub LockScreenFootnote: Option<String>,
+    pub AssetTagInformation: Option<String>,
+}
+
+impl MobileconfLockScreen {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        // Apple ships these keys in the shared device configuration payload
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if !(typ == *"com.apple.lockscreen" || typ == *"com.apple.shareddeviceconfiguration") {
+                return Result::Err(MobileconfError::NotLockScreen);
+            }
+        }
+
+        Result::Ok(MobileconfLockScreen {
+            LockScreenFootnote: get_string_opt(dict, "LockScreenFootnote")?,
+            AssetTagInformation: get_string_opt(dict, "AssetTagInformation")?,
+        })
+    }
+}
+
+impl DescribePayload for MobileconfLockScreen {
+    const PAYLOAD_TYPES: &'static [&'static str] = &[
+        "com.apple.lockscreen",
+        "com.apple.shareddeviceconfiguration",
+    ];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("LockScreenFootnote", "Option<String>"),
+        ("AssetTagInformation", "Option<String>"),
+    ];
+}
@@ -0,0 +1,53 @@
+use crate::dict::{as_payload, get_string, get_string_opt};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+
+/// Configuration for a managed app. `com.apple.vpn.managed.applayer` payloads
+/// look related but hold per-app VPN settings and no bundle identifier, so
+/// [`MobileconfVPN`] reads those.
+///
+/// [`MobileconfVPN`]: crate::MobileconfVPN
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfManagedApp {
+    pub BundleIdentifier: String,
+    // app specific, so kept untyped
+    pub Configuration: Option<serde_json::Value>,
+}
+
+impl MobileconfManagedApp {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.app.managed" {
+                return Result::Err(MobileconfError::NotManagedApp);
+            }
+        }
+
+        let BundleIdentifier = match get_string_opt(dict, "BundleIdentifier")? {
+            Some(identifier) => identifier,
+            None => get_string(dict, "Identifier")?,
+        };
+
+        let Configuration = match dict.get("Configuration") {
+            Some(config) => Some(serde_json::to_value(config)?),
+            None => None,
+        };
+
+        Result::Ok(MobileconfManagedApp {
+            BundleIdentifier,
+            Configuration,
+        })
+    }
+}
+
+impl DescribePayload for MobileconfManagedApp {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.app.managed"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("BundleIdentifier", "String"),
+        ("Configuration", "Option<serde_json::Value>"),
+    ];
+}
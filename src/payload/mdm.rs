@@ -0,0 +1,79 @@
+use crate::dict::{as_payload, get_int_opt, get_string, get_string_opt};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+
+/// The bits of `AccessRights` and what each lets the server do.
+const ACCESS_RIGHTS: &[(i64, &str)] = &[
+    (1, "inspect configuration profiles"),
+    (2, "install and remove configuration profiles"),
+    (4, "lock device and remove passcode"),
+    (8, "erase device"),
+    (16, "query device information"),
+    (32, "query network information"),
+    (64, "inspect provisioning profiles"),
+    (128, "install and remove provisioning profiles"),
+    (256, "inspect installed apps"),
+    (512, "query restrictions"),
+    (1024, "query security"),
+    (2048, "change settings"),
+    (4096, "manage apps"),
+];
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfMDM {
+    pub ServerURL: String,
+    pub CheckInURL: Option<String>,
+    // APNs topic the device listens on
+    pub Topic: String,
+    pub AccessRights: i64,
+    // AccessRights spelled out
+    pub access_rights: Vec<&'static str>,
+    // payload holding the identity the device authenticates with
+    pub IdentityCertificateUUID: String,
+}
+
+impl MobileconfMDM {
+    #[allow(non_snake_case)]
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.mdm" {
+                return Result::Err(MobileconfError::NotMDM);
+            }
+        }
+
+        let AccessRights = get_int_opt(dict, "AccessRights")?
+            .ok_or_else(|| MobileconfError::MissingKey("AccessRights".to_string()))?;
+
+        let access_rights = ACCESS_RIGHTS
+            .iter()
+            .filter(|&&(bit, _)| AccessRights & bit != 0)
+            .map(|&(_, name)| name)
+            .collect();
+
+        Result::Ok(MobileconfMDM {
+            ServerURL: get_string(dict, "ServerURL")?,
+            CheckInURL: get_string_opt(dict, "CheckInURL")?,
+            Topic: get_string(dict, "Topic")?,
+            AccessRights,
+            access_rights,
+            IdentityCertificateUUID: get_string(dict, "IdentityCertificateUUID")?,
+        })
+    }
+}
+
+impl DescribePayload for MobileconfMDM {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.mdm"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("ServerURL", "String"),
+        ("CheckInURL", "Option<String>"),
+        ("Topic", "String"),
+        ("AccessRights", "i64"),
+        ("access_rights", "Vec<&'static str>"),
+        ("IdentityCertificateUUID", "String"),
+    ];
+}
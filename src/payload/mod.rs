@@ -0,0 +1,306 @@
+mod airplay;
+mod airprint;
+mod applock;
+mod associateddomains;
+mod caldav;
+mod carddav;
+mod cellular;
+mod cert;
+mod certtransparency;
+mod contentfilter;
+mod dns;
+mod dnsproxy;
+mod email;
+mod exchange;
+mod extensiblesso;
+mod font;
+mod homescreen;
+mod ldap;
+mod lockscreen;
+mod managedapp;
+mod mdm;
+mod notifications;
+mod passcode;
+mod pkcs12;
+mod proxy;
+mod relay;
+mod restrictions;
+mod scep;
+mod sso;
+mod subscribedcalendar;
+mod vpn;
+mod wallpaper;
+mod webclip;
+mod wifi;
+
+pub use airplay::{AirPlayPassword, MobileconfAirPlaySecurity};
+pub use airprint::{AirPrintTarget, MobileconfAirPrint};
+pub use applock::MobileconfAppLock;
+pub use associateddomains::MobileconfAssociatedDomains;
+pub use caldav::MobileconfCalDAV;
+pub use carddav::MobileconfCardDAV;
+pub use cellular::{CellularAPN, MobileconfCellular};
+pub use cert::{dump_certs, CertFormat, MobileconfTLSCert};
+pub use certtransparency::MobileconfCertTransparency;
+pub use contentfilter::{Bookmark, MobileconfContentFilter};
+pub use dns::MobileconfDNS;
+pub use dnsproxy::MobileconfDNSProxy;
+pub use email::MobileconfEmail;
+pub use exchange::MobileconfExchange;
+pub use extensiblesso::MobileconfExtensibleSSO;
+pub use font::{dump_fonts, MobileconfFont};
+pub use homescreen::{HomeScreenItem, MobileconfHomeScreen};
+pub use ldap::{LdapSearch, MobileconfLDAP};
+pub use lockscreen::MobileconfLockScreen;
+pub use managedapp::MobileconfManagedApp;
+pub use mdm::MobileconfMDM;
+pub use notifications::{AppNotificationSetting, MobileconfNotifications};
+pub use passcode::MobileconfPasscode;
+pub use pkcs12::MobileconfPKCS12;
+pub use proxy::MobileconfProxy;
+pub use relay::{MobileconfRelay, Relay};
+pub use restrictions::MobileconfRestrictions;
+pub use scep::MobileconfSCEP;
+pub use sso::MobileconfSSO;
+pub use subscribedcalendar::MobileconfSubscribedCalendar;
+pub use vpn::{MobileconfVPN, OnDemandRule};
+pub use wallpaper::{dump_wallpapers, MobileconfWallpaper};
+pub use webclip::MobileconfWebClip;
+pub use wifi::{EAPType, MobileconfPersonalWifi, MobileconfWifi, WifiProxy};
+
+use crate::MobileconfError;
+use serde::Serialize;
+use std::any::type_name;
+use std::str::FromStr;
+
+/// What a parser extracts, see [`describe_payloads`].
+pub trait DescribePayload {
+    /// The PayloadTypes the parser accepts.
+    const PAYLOAD_TYPES: &'static [&'static str];
+    /// Names and types of the struct's fields.
+    const FIELDS: &'static [(&'static str, &'static str)];
+}
+
+#[derive(Debug, Serialize)]
+pub struct FieldDescription {
+    pub name: &'static str,
+    #[serde(rename = "type")]
+    pub typ: &'static str,
+}
+
+/// A parser, the payload types it handles and the fields it extracts.
+#[derive(Debug, Serialize)]
+pub struct PayloadDescription {
+    pub name: &'static str,
+    pub payload_types: &'static [&'static str],
+    pub fields: Vec<FieldDescription>,
+}
+
+fn describe<T: DescribePayload>() -> PayloadDescription {
+    PayloadDescription {
+        name: type_name::<T>().rsplit("::").next().unwrap_or_default(),
+        payload_types: T::PAYLOAD_TYPES,
+        fields: T::FIELDS
+            .iter()
+            .map(|&(name, typ)| FieldDescription { name, typ })
+            .collect(),
+    }
+}
+
+/// Every parser we have, in the order of the `ParsedProfile` fields.
+pub fn describe_payloads() -> Vec<PayloadDescription> {
+    vec![
+        describe::<MobileconfWifi>(),
+        describe::<MobileconfPersonalWifi>(),
+        describe::<MobileconfTLSCert>(),
+        describe::<MobileconfPKCS12>(),
+        describe::<MobileconfVPN>(),
+        describe::<MobileconfEmail>(),
+        describe::<MobileconfExchange>(),
+        describe::<MobileconfWebClip>(),
+        describe::<MobileconfCalDAV>(),
+        describe::<MobileconfCardDAV>(),
+        describe::<MobileconfLDAP>(),
+        describe::<MobileconfPasscode>(),
+        describe::<MobileconfRestrictions>(),
+        describe::<MobileconfDNS>(),
+        describe::<MobileconfProxy>(),
+        describe::<MobileconfSCEP>(),
+        describe::<MobileconfAirPrint>(),
+        describe::<MobileconfSubscribedCalendar>(),
+        describe::<MobileconfCellular>(),
+        describe::<MobileconfExtensibleSSO>(),
+        describe::<MobileconfContentFilter>(),
+        describe::<MobileconfFont>(),
+        describe::<MobileconfManagedApp>(),
+        describe::<MobileconfSSO>(),
+        describe::<MobileconfAirPlaySecurity>(),
+        describe::<MobileconfNotifications>(),
+        describe::<MobileconfLockScreen>(),
+        describe::<MobileconfDNSProxy>(),
+        describe::<MobileconfAssociatedDomains>(),
+        describe::<MobileconfHomeScreen>(),
+        describe::<MobileconfCertTransparency>(),
+        describe::<MobileconfWallpaper>(),
+        describe::<MobileconfRelay>(),
+        describe::<MobileconfMDM>(),
+        describe::<MobileconfAppLock>(),
+    ]
+}
+
+/// What came of running one parser over one payload.
+#[derive(Debug)]
+pub enum Parsed<T> {
+    Matched(T),
+    // the payload is of another type, which is not an error
+    NotThisType,
+    Err(MobileconfError),
+}
+
+impl<T> Parsed<T> {
+    /// The result for payloads of this type, `None` for the others.
+    pub fn matched(self) -> Option<Result<T, MobileconfError>> {
+        match self {
+            Parsed::Matched(payload) => Some(Result::Ok(payload)),
+            Parsed::NotThisType => None,
+            Parsed::Err(err) => Some(Result::Err(err)),
+        }
+    }
+}
+
+impl<T> From<Result<T, MobileconfError>> for Parsed<T> {
+    fn from(result: Result<T, MobileconfError>) -> Self {
+        match result {
+            Result::Ok(payload) => Parsed::Matched(payload),
+            Result::Err(err) if err.is_wrong_payload_type() => Parsed::NotThisType,
+            Result::Err(err) => Parsed::Err(err),
+        }
+    }
+}
+
+/// The categories of payloads we have parsers for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PayloadKind {
+    Wifi,
+    Cert,
+    Vpn,
+    Email,
+    Exchange,
+    WebClip,
+    CalDav,
+    CardDav,
+    Ldap,
+    Passcode,
+    Restrictions,
+    Dns,
+    Proxy,
+    Scep,
+    AirPrint,
+    SubscribedCalendar,
+    Cellular,
+    ExtensibleSso,
+    ContentFilter,
+    Font,
+    ManagedApp,
+    Sso,
+    AirPlay,
+    Notifications,
+    LockScreen,
+    DnsProxy,
+    AssociatedDomains,
+    HomeScreen,
+    CertTransparency,
+    Wallpaper,
+    Relay,
+    Mdm,
+    AppLock,
+}
+
+impl PayloadKind {
+    /// The parser for payloads of this PayloadType, `None` if we have none.
+    pub fn of(payload_type: &str) -> Option<Self> {
+        match payload_type {
+            "com.apple.wifi.managed" => Some(PayloadKind::Wifi),
+            "com.apple.security.pem" | "com.apple.security.root" | "com.apple.security.pkcs12" => {
+                Some(PayloadKind::Cert)
+            }
+            "com.apple.vpn.managed" | "com.apple.vpn.managed.applayer" => Some(PayloadKind::Vpn),
+            "com.apple.mail.managed" => Some(PayloadKind::Email),
+            "com.apple.eas.account" => Some(PayloadKind::Exchange),
+            "com.apple.webClip.managed" => Some(PayloadKind::WebClip),
+            "com.apple.caldav.account" => Some(PayloadKind::CalDav),
+            "com.apple.carddav.account" => Some(PayloadKind::CardDav),
+            "com.apple.ldap.account" => Some(PayloadKind::Ldap),
+            "com.apple.mobiledevice.passwordpolicy" => Some(PayloadKind::Passcode),
+            "com.apple.applicationaccess" => Some(PayloadKind::Restrictions),
+            "com.apple.dnsSettings.managed" => Some(PayloadKind::Dns),
+            "com.apple.proxy.http.global" => Some(PayloadKind::Proxy),
+            "com.apple.security.scep" => Some(PayloadKind::Scep),
+            "com.apple.airprint" => Some(PayloadKind::AirPrint),
+            "com.apple.subscribedcalendar.account" => Some(PayloadKind::SubscribedCalendar),
+            "com.apple.cellular" => Some(PayloadKind::Cellular),
+            "com.apple.extensiblesso" => Some(PayloadKind::ExtensibleSso),
+            "com.apple.webcontent-filter" => Some(PayloadKind::ContentFilter),
+            "com.apple.font" => Some(PayloadKind::Font),
+            "com.apple.app.managed" => Some(PayloadKind::ManagedApp),
+            "com.apple.sso" => Some(PayloadKind::Sso),
+            "com.apple.airplay.security" | "com.apple.airplay" => Some(PayloadKind::AirPlay),
+            "com.apple.notificationsettings" => Some(PayloadKind::Notifications),
+            "com.apple.lockscreen" | "com.apple.shareddeviceconfiguration" => {
+                Some(PayloadKind::LockScreen)
+            }
+            "com.apple.dnsProxy.managed" => Some(PayloadKind::DnsProxy),
+            "com.apple.associated-domains" => Some(PayloadKind::AssociatedDomains),
+            "com.apple.homescreenlayout" => Some(PayloadKind::HomeScreen),
+            "com.apple.security.certificatetransparency" => Some(PayloadKind::CertTransparency),
+            "com.apple.wallpaper" => Some(PayloadKind::Wallpaper),
+            "com.apple.relay.managed" => Some(PayloadKind::Relay),
+            "com.apple.mdm" => Some(PayloadKind::Mdm),
+            "com.apple.app.lock" => Some(PayloadKind::AppLock),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for PayloadKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "wifi" => Result::Ok(PayloadKind::Wifi),
+            "cert" => Result::Ok(PayloadKind::Cert),
+            "vpn" => Result::Ok(PayloadKind::Vpn),
+            "email" => Result::Ok(PayloadKind::Email),
+            "exchange" => Result::Ok(PayloadKind::Exchange),
+            "webclip" => Result::Ok(PayloadKind::WebClip),
+            "caldav" => Result::Ok(PayloadKind::CalDav),
+            "carddav" => Result::Ok(PayloadKind::CardDav),
+            "ldap" => Result::Ok(PayloadKind::Ldap),
+            "passcode" => Result::Ok(PayloadKind::Passcode),
+            "restrictions" => Result::Ok(PayloadKind::Restrictions),
+            "dns" => Result::Ok(PayloadKind::Dns),
+            "proxy" => Result::Ok(PayloadKind::Proxy),
+            "scep" => Result::Ok(PayloadKind::Scep),
+            "airprint" => Result::Ok(PayloadKind::AirPrint),
+            "subscribedcalendar" => Result::Ok(PayloadKind::SubscribedCalendar),
+            "cellular" => Result::Ok(PayloadKind::Cellular),
+            "extensiblesso" => Result::Ok(PayloadKind::ExtensibleSso),
+            "contentfilter" => Result::Ok(PayloadKind::ContentFilter),
+            "font" => Result::Ok(PayloadKind::Font),
+            "managedapp" => Result::Ok(PayloadKind::ManagedApp),
+            "sso" => Result::Ok(PayloadKind::Sso),
+            "airplay" => Result::Ok(PayloadKind::AirPlay),
+            "notifications" => Result::Ok(PayloadKind::Notifications),
+            "lockscreen" => Result::Ok(PayloadKind::LockScreen),
+            "dnsproxy" => Result::Ok(PayloadKind::DnsProxy),
+            "associateddomains" => Result::Ok(PayloadKind::AssociatedDomains),
+            "homescreen" => Result::Ok(PayloadKind::HomeScreen),
+            "certtransparency" => Result::Ok(PayloadKind::CertTransparency),
+            "wallpaper" => Result::Ok(PayloadKind::Wallpaper),
+            "relay" => Result::Ok(PayloadKind::Relay),
+            "mdm" => Result::Ok(PayloadKind::Mdm),
+            "applock" => Result::Ok(PayloadKind::AppLock),
+            _ => Result::Err(format!("unknown payload type: {}", s)),
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use crate::dict::{as_payload, get_array_opt, get_bool_opt, get_int_opt, get_string};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+
+/// Notification settings for a single app.
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct AppNotificationSetting {
+    pub BundleIdentifier: String,
+    pub NotificationsEnabled: Option<bool>,
+    // 0 none, 1 banner, 2 modal alert
+    pub AlertType: Option<i64>,
+    pub BadgesEnabled: Option<bool>,
+    pub SoundsEnabled: Option<bool>,
+}
+
+impl AppNotificationSetting {
+    fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = v
+            .as_dictionary()
+            .ok_or_else(|| MobileconfError::wrong_type("NotificationSettings", "dictionary"))?;
+
+        Result::Ok(AppNotificationSetting {
+            BundleIdentifier: get_string(dict, "BundleIdentifier")?,
+            NotificationsEnabled: get_bool_opt(dict, "NotificationsEnabled")?,
+            AlertType: get_int_opt(dict, "AlertType")?,
+            BadgesEnabled: get_bool_opt(dict, "BadgesEnabled")?,
+            SoundsEnabled: get_bool_opt(dict, "SoundsEnabled")?,
+        })
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfNotifications {
+    pub NotificationSettings: Vec<AppNotificationSetting>,
+}
+
+impl MobileconfNotifications {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.notificationsettings" {
+                return Result::Err(MobileconfError::NotNotifications);
+            }
+        }
+
+        let NotificationSettings = match get_array_opt(dict, "NotificationSettings")? {
+            Some(settings) => settings
+                .iter()
+                .map(AppNotificationSetting::parse)
+                .collect::<Result<Vec<_>, _>>()?,
+            None => Vec::new(),
+        };
+
+        Result::Ok(MobileconfNotifications {
+            NotificationSettings,
+        })
+    }
+}
+
+impl DescribePayload for MobileconfNotifications {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.notificationsettings"];
+    const FIELDS: &'static [(&'static str, &'static str)] =
+        &[("NotificationSettings", "Vec<AppNotificationSetting>")];
+}
@@ -0,0 +1,55 @@
+use crate::dict::{as_payload, get_bool_opt, get_int_opt, get_string};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfPasscode {
+    pub allowSimple: bool,
+    pub forcePIN: bool,
+    pub requireAlphanumeric: bool,
+    // the limits are None when the policy doesn't set them
+    pub minLength: Option<i64>,
+    pub maxFailedAttempts: Option<i64>,
+    // minutes
+    pub maxInactivity: Option<i64>,
+    pub maxPINAgeInDays: Option<i64>,
+}
+
+impl MobileconfPasscode {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.mobiledevice.passwordpolicy" {
+                return Result::Err(MobileconfError::NotPasscode);
+            }
+        }
+
+        // absent keys fall back to Apple's defaults, which enforce nothing
+        Result::Ok(MobileconfPasscode {
+            allowSimple: get_bool_opt(dict, "allowSimple")?.unwrap_or(true),
+            forcePIN: get_bool_opt(dict, "forcePIN")?.unwrap_or(false),
+            requireAlphanumeric: get_bool_opt(dict, "requireAlphanumeric")?.unwrap_or(false),
+            minLength: get_int_opt(dict, "minLength")?,
+            maxFailedAttempts: get_int_opt(dict, "maxFailedAttempts")?,
+            maxInactivity: get_int_opt(dict, "maxInactivity")?,
+            maxPINAgeInDays: get_int_opt(dict, "maxPINAgeInDays")?,
+        })
+    }
+}
+
+impl DescribePayload for MobileconfPasscode {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.mobiledevice.passwordpolicy"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("allowSimple", "bool"),
+        ("forcePIN", "bool"),
+        ("requireAlphanumeric", "bool"),
+        ("minLength", "Option<i64>"),
+        ("maxFailedAttempts", "Option<i64>"),
+        ("maxInactivity", "Option<i64>"),
+        ("maxPINAgeInDays", "Option<i64>"),
+    ];
+}
@@ -0,0 +1,66 @@
+use crate::certinfo::format_name;
+use crate::dict::{as_payload, get_string, get_string_opt, get_value};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use crate::Secret;
+use openssl::pkcs12::Pkcs12;
+use plist::Value;
+use serde::Serialize;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfPKCS12 {
+    pub PayloadUUID: String,
+    // pkcs12 bytes
+    pub PayloadContent: String,
+    pub Password: Option<Secret>,
+    // subject of the contained certificate, only known when Password is set
+    pub CertificateSubject: Option<String>,
+}
+
+impl MobileconfPKCS12 {
+    #[allow(non_snake_case)]
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.security.pkcs12" {
+                return Result::Err(MobileconfError::NotPKCS12);
+            }
+        }
+
+        let PayloadUUID = get_string(dict, "PayloadUUID")?;
+
+        let data: &[u8] = get_value(dict, "PayloadContent")?
+            .as_data()
+            .ok_or_else(|| MobileconfError::wrong_type("PayloadContent", "data"))?;
+
+        let Password = get_string_opt(dict, "Password")?.map(Secret::from);
+
+        let CertificateSubject = match &Password {
+            Some(password) => Pkcs12::from_der(data)
+                .and_then(|pkcs12| pkcs12.parse2(password.expose()))
+                .map_err(MobileconfError::Pkcs12)?
+                .cert
+                .map(|cert| format_name(cert.subject_name())),
+            None => None,
+        };
+
+        Result::Ok(MobileconfPKCS12 {
+            PayloadUUID,
+            PayloadContent: base64::encode(data),
+            Password,
+            CertificateSubject,
+        })
+    }
+}
+
+impl DescribePayload for MobileconfPKCS12 {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.security.pkcs12"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("PayloadUUID", "String"),
+        ("PayloadContent", "String"),
+        ("Password", "Option<Secret>"),
+        ("CertificateSubject", "Option<String>"),
+    ];
+}
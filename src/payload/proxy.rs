@@ -0,0 +1,68 @@
+use crate::dict::{as_payload, get_int_opt, get_string, get_string_opt};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfProxy {
+    // Manual or Auto
+    pub ProxyType: String,
+    // required for Manual proxies
+    pub ProxyServer: Option<String>,
+    pub ProxyServerPort: Option<i64>,
+    pub ProxyUsername: Option<String>,
+    // required for Auto proxies
+    pub ProxyPACURL: Option<String>,
+}
+
+impl MobileconfProxy {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.proxy.http.global" {
+                return Result::Err(MobileconfError::NotProxy);
+            }
+        }
+
+        let ProxyType = get_string(dict, "ProxyType")?;
+
+        let (ProxyServer, ProxyServerPort, ProxyPACURL) =
+            match ProxyType.as_str() {
+                "Manual" => (
+                    Some(get_string(dict, "ProxyServer")?),
+                    Some(get_int_opt(dict, "ProxyServerPort")?.ok_or_else(|| {
+                        MobileconfError::MissingKey("ProxyServerPort".to_string())
+                    })?),
+                    None,
+                ),
+                "Auto" => (None, None, Some(get_string(dict, "ProxyPACURL")?)),
+                _ => (
+                    get_string_opt(dict, "ProxyServer")?,
+                    get_int_opt(dict, "ProxyServerPort")?,
+                    get_string_opt(dict, "ProxyPACURL")?,
+                ),
+            };
+
+        Result::Ok(MobileconfProxy {
+            ProxyType,
+            ProxyServer,
+            ProxyServerPort,
+            ProxyUsername: get_string_opt(dict, "ProxyUsername")?,
+            ProxyPACURL,
+        })
+    }
+}
+
+impl DescribePayload for MobileconfProxy {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.proxy.http.global"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("ProxyType", "String"),
+        ("ProxyServer", "Option<String>"),
+        ("ProxyServerPort", "Option<i64>"),
+        ("ProxyUsername", "Option<String>"),
+        ("ProxyPACURL", "Option<String>"),
+    ];
+}
@@ -0,0 +1,79 @@
+use crate::dict::{as_payload, get_array_opt, get_string, get_string_opt};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// One relay server, reachable over HTTP/3, HTTP/2 or both.
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct Relay {
+    pub HTTP3RelayURL: Option<String>,
+    pub HTTP2RelayURL: Option<String>,
+    pub AdditionalHTTPHeaderFields: BTreeMap<String, String>,
+}
+
+impl Relay {
+    fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = v
+            .as_dictionary()
+            .ok_or_else(|| MobileconfError::wrong_type("Relays", "dictionary"))?;
+
+        let AdditionalHTTPHeaderFields = match dict.get("AdditionalHTTPHeaderFields") {
+            Some(fields) => fields
+                .as_dictionary()
+                .ok_or_else(|| {
+                    MobileconfError::wrong_type("AdditionalHTTPHeaderFields", "dictionary")
+                })?
+                .iter()
+                .map(|(name, value)| {
+                    value
+                        .as_string()
+                        .map(|value| (name.clone(), value.to_string()))
+                        .ok_or_else(|| MobileconfError::wrong_type(name, "string"))
+                })
+                .collect::<Result<BTreeMap<_, _>, _>>()?,
+            None => BTreeMap::new(),
+        };
+
+        Result::Ok(Relay {
+            HTTP3RelayURL: get_string_opt(dict, "HTTP3RelayURL")?,
+            HTTP2RelayURL: get_string_opt(dict, "HTTP2RelayURL")?,
+            AdditionalHTTPHeaderFields,
+        })
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfRelay {
+    pub Relays: Vec<Relay>,
+}
+
+impl MobileconfRelay {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.relay.managed" {
+                return Result::Err(MobileconfError::NotRelay);
+            }
+        }
+
+        let Relays = match get_array_opt(dict, "Relays")? {
+            Some(relays) => relays
+                .iter()
+                .map(Relay::parse)
+                .collect::<Result<Vec<_>, _>>()?,
+            None => Vec::new(),
+        };
+
+        Result::Ok(MobileconfRelay { Relays })
+    }
+}
+
+impl DescribePayload for MobileconfRelay {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.relay.managed"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[("Relays", "Vec<Relay>")];
+}
@@ -0,0 +1,39 @@
+use crate::dict::{as_payload, get_string};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfRestrictions {
+    // every boolean key of the payload, such as allowCamera, so new keys
+    // Apple adds show up without changes here
+    pub Restrictions: BTreeMap<String, bool>,
+}
+
+impl MobileconfRestrictions {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.applicationaccess" {
+                return Result::Err(MobileconfError::NotRestrictions);
+            }
+        }
+
+        let Restrictions = dict
+            .iter()
+            .filter_map(|(key, value)| value.as_boolean().map(|b| (key.clone(), b)))
+            .collect();
+
+        Result::Ok(MobileconfRestrictions { Restrictions })
+    }
+}
+
+impl DescribePayload for MobileconfRestrictions {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.applicationaccess"];
+    const FIELDS: &'static [(&'static str, &'static str)] =
+        &[("Restrictions", "BTreeMap<String, bool>")];
+}
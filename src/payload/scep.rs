@@ -0,0 +1,67 @@
+use crate::dict::{as_payload, get_array_opt, get_dict, get_int_opt, get_string, get_string_opt};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+
+/// SCEP enrollment settings, taken from the nested `PayloadContent` dictionary.
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfSCEP {
+    pub URL: String,
+    pub Name: Option<String>,
+    // flattened to `C=US, O=Org, CN=example`
+    pub Subject: Option<String>,
+    pub Keysize: Option<i64>,
+    // bitmask, 1 is signing and 4 is encryption
+    pub KeyUsage: Option<i64>,
+}
+
+impl MobileconfSCEP {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.security.scep" {
+                return Result::Err(MobileconfError::NotSCEP);
+            }
+        }
+
+        let content = get_dict(dict, "PayloadContent")?;
+
+        Result::Ok(MobileconfSCEP {
+            URL: get_string(content, "URL")?,
+            Name: get_string_opt(content, "Name")?,
+            Subject: get_array_opt(content, "Subject")?
+                .map(Vec::as_slice)
+                .map(format_subject),
+            Keysize: get_int_opt(content, "Keysize")?,
+            KeyUsage: get_int_opt(content, "Key Usage")?,
+        })
+    }
+}
+
+/// `Subject` is a list of relative names, each a list of `[OID, value]` pairs.
+fn format_subject(subject: &[Value]) -> String {
+    subject
+        .iter()
+        .filter_map(Value::as_array)
+        .flatten()
+        .filter_map(|pair| match pair.as_array().map(Vec::as_slice) {
+            Some([oid, value]) => Some(format!("{}={}", oid.as_string()?, value.as_string()?)),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl DescribePayload for MobileconfSCEP {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.security.scep"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("URL", "String"),
+        ("Name", "Option<String>"),
+        ("Subject", "Option<String>"),
+        ("Keysize", "Option<i64>"),
+        ("KeyUsage", "Option<i64>"),
+    ];
+}
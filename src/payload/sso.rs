@@ -0,0 +1,69 @@
+use crate::dict::{as_payload, get_string, get_string_opt, get_strings_opt};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::{Dictionary, Value};
+use serde::Serialize;
+
+/// The legacy Kerberos single sign-on payload, see [`MobileconfExtensibleSSO`]
+/// for its replacement.
+///
+/// [`MobileconfExtensibleSSO`]: crate::MobileconfExtensibleSSO
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfSSO {
+    pub Name: String,
+    // from the nested Kerberos dictionary
+    pub PrincipalName: Option<String>,
+    pub Realm: Option<String>,
+    pub Hosts: Vec<String>,
+    pub URLPrefixMatches: Vec<String>,
+}
+
+impl MobileconfSSO {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.sso" {
+                return Result::Err(MobileconfError::NotSSO);
+            }
+        }
+
+        let empty = Dictionary::new();
+        let kerberos = match dict.get("Kerberos") {
+            Some(kerberos) => kerberos
+                .as_dictionary()
+                .ok_or_else(|| MobileconfError::wrong_type("Kerberos", "dictionary"))?,
+            None => &empty,
+        };
+
+        // the match lists normally sit inside Kerberos, but older profiles
+        // have them next to it
+        let strings = |key: &str| -> Result<Vec<String>, MobileconfError> {
+            let values = match get_strings_opt(kerberos, key)? {
+                Some(values) => Some(values),
+                None => get_strings_opt(dict, key)?,
+            };
+            Result::Ok(values.unwrap_or_default())
+        };
+
+        Result::Ok(MobileconfSSO {
+            Name: get_string(dict, "Name")?,
+            PrincipalName: get_string_opt(kerberos, "PrincipalName")?,
+            Realm: get_string_opt(kerberos, "Realm")?,
+            Hosts: strings("Hosts")?,
+            URLPrefixMatches: strings("URLPrefixMatches")?,
+        })
+    }
+}
+
+impl DescribePayload for MobileconfSSO {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.sso"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("Name", "String"),
+        ("PrincipalName", "Option<String>"),
+        ("Realm", "Option<String>"),
+        ("Hosts", "Vec<String>"),
+        ("URLPrefixMatches", "Vec<String>"),
+    ];
+}
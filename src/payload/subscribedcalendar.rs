@@ -0,0 +1,45 @@
+use crate::dict::{as_payload, get_bool_opt, get_string, get_string_opt};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfSubscribedCalendar {
+    pub SubCalAccountDescription: Option<String>,
+    // the calendar URL
+    pub SubCalAccountHostName: String,
+    pub SubCalAccountUseSSL: bool,
+    pub SubCalAccountUsername: Option<String>,
+}
+
+impl MobileconfSubscribedCalendar {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.subscribedcalendar.account" {
+                return Result::Err(MobileconfError::NotSubscribedCalendar);
+            }
+        }
+
+        Result::Ok(MobileconfSubscribedCalendar {
+            SubCalAccountDescription: get_string_opt(dict, "SubCalAccountDescription")?,
+            SubCalAccountHostName: get_string(dict, "SubCalAccountHostName")?,
+            // https unless told otherwise, like CalDAV and CardDAV
+            SubCalAccountUseSSL: get_bool_opt(dict, "SubCalAccountUseSSL")?.unwrap_or(true),
+            SubCalAccountUsername: get_string_opt(dict, "SubCalAccountUsername")?,
+        })
+    }
+}
+
+impl DescribePayload for MobileconfSubscribedCalendar {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.subscribedcalendar.account"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("SubCalAccountDescription", "Option<String>"),
+        ("SubCalAccountHostName", "String"),
+        ("SubCalAccountUseSSL", "bool"),
+        ("SubCalAccountUsername", "Option<String>"),
+    ];
+}
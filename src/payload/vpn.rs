@@ -0,0 +1,117 @@
+use crate::dict::{
+    as_payload, get_array_opt, get_dict, get_int_opt, get_string, get_string_opt, get_strings_opt,
+};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+
+/// An entry of `OnDemandRules`, deciding whether the VPN connects on its own.
+/// A rule applies when all of its match keys that are set match.
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct OnDemandRule {
+    // Connect, Disconnect, EvaluateConnection or Ignore
+    pub Action: String,
+    // Ethernet, WiFi or Cellular
+    pub InterfaceTypeMatch: Option<String>,
+    pub SSIDMatch: Vec<String>,
+    pub DNSDomainMatch: Vec<String>,
+    pub URLStringProbe: Option<String>,
+}
+
+impl OnDemandRule {
+    fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = v
+            .as_dictionary()
+            .ok_or_else(|| MobileconfError::wrong_type("OnDemandRules", "dictionary"))?;
+
+        Result::Ok(OnDemandRule {
+            Action: get_string(dict, "Action")?,
+            InterfaceTypeMatch: get_string_opt(dict, "InterfaceTypeMatch")?,
+            SSIDMatch: get_strings_opt(dict, "SSIDMatch")?.unwrap_or_default(),
+            DNSDomainMatch: get_strings_opt(dict, "DNSDomainMatch")?.unwrap_or_default(),
+            URLStringProbe: get_string_opt(dict, "URLStringProbe")?,
+        })
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfVPN {
+    pub VPNType: String,
+    pub UserDefinedName: String,
+    // server address, read from the dictionary matching VPNType
+    pub RemoteAddress: String,
+    // from the same dictionary; the rules are kept when on demand is off,
+    // but only take effect when OnDemandEnabled is set
+    pub OnDemandEnabled: bool,
+    pub OnDemandRules: Vec<OnDemandRule>,
+    // per-app VPN, from com.apple.vpn.managed.applayer payloads: what apps
+    // and Safari domains refer to this VPN by
+    pub VPNUUID: Option<String>,
+    pub SafariDomains: Vec<String>,
+}
+
+impl MobileconfVPN {
+    /// Returns `Ok(None)` for VPN types we don't know how to read.
+    #[allow(non_snake_case)]
+    pub fn parse(v: &Value) -> Result<Option<Self>, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if !(typ == *"com.apple.vpn.managed" || typ == *"com.apple.vpn.managed.applayer") {
+                return Result::Err(MobileconfError::NotVPN);
+            }
+        }
+
+        let VPNType = get_string(dict, "VPNType")?;
+
+        let (server_key, address_key) = match VPNType.as_str() {
+            "IKEv2" => ("IKEv2", "RemoteAddress"),
+            "IPSec" => ("IPSec", "RemoteAddress"),
+            "L2TP" | "PPTP" => ("PPP", "CommRemoteAddress"),
+            "VPN" => ("VPN", "RemoteAddress"),
+            _ => return Result::Ok(None),
+        };
+
+        let UserDefinedName = get_string(dict, "UserDefinedName")?;
+
+        let server = get_dict(dict, server_key)?;
+
+        let RemoteAddress = get_string(server, address_key)?;
+
+        let OnDemandEnabled = get_int_opt(server, "OnDemandEnabled")? == Some(1);
+        let OnDemandRules = match get_array_opt(server, "OnDemandRules")? {
+            Some(rules) => rules
+                .iter()
+                .map(OnDemandRule::parse)
+                .collect::<Result<Vec<_>, _>>()?,
+            None => Vec::new(),
+        };
+
+        Result::Ok(Some(MobileconfVPN {
+            VPNType,
+            UserDefinedName,
+            RemoteAddress,
+            OnDemandEnabled,
+            OnDemandRules,
+            VPNUUID: get_string_opt(dict, "VPNUUID")?,
+            SafariDomains: get_strings_opt(dict, "SafariDomains")?.unwrap_or_default(),
+        }))
+    }
+}
+
+impl DescribePayload for MobileconfVPN {
+    const PAYLOAD_TYPES: &'static [&'static str] =
+        &["com.apple.vpn.managed", "com.apple.vpn.managed.applayer"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("VPNType", "String"),
+        ("UserDefinedName", "String"),
+        ("RemoteAddress", "String"),
+        ("OnDemandEnabled", "bool"),
+        ("OnDemandRules", "Vec<OnDemandRule>"),
+        ("VPNUUID", "Option<String>"),
+        ("SafariDomains", "Vec<String>"),
+    ];
+}
@@ -0,0 +1,88 @@
+use crate::dict::{as_payload, get_int_opt, get_string, get_value};
+use crate::files::payload_file;
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfWallpaper {
+    pub PayloadUUID: String,
+    // image file bytes
+    pub Image: String,
+    // 1 lock screen, 2 home screen, 3 both
+    pub Locations: Option<i64>,
+}
+
+impl MobileconfWallpaper {
+    #[allow(non_snake_case)]
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.wallpaper" {
+                return Result::Err(MobileconfError::NotWallpaper);
+            }
+        }
+
+        let data: &[u8] = get_value(dict, "Image")?
+            .as_data()
+            .ok_or_else(|| MobileconfError::wrong_type("Image", "data"))?;
+
+        Result::Ok(MobileconfWallpaper {
+            PayloadUUID: get_string(dict, "PayloadUUID")?,
+            Image: base64::encode(data),
+            Locations: get_int_opt(dict, "Locations")?,
+        })
+    }
+
+    /// File extension for the image, going by its magic bytes.
+    pub fn extension(&self) -> Result<&'static str, MobileconfError> {
+        let data = base64::decode(&self.Image)?;
+
+        let extension = if data.starts_with(b"\x89PNG\r\n\x1a\n") {
+            "png"
+        } else if data.starts_with(b"\xff\xd8\xff") {
+            "jpg"
+        } else {
+            "bin"
+        };
+
+        Result::Ok(extension)
+    }
+}
+
+/// Write each wallpaper to `dir/<PayloadUUID>.<ext>`, creating `dir` if needed.
+pub fn dump_wallpapers(
+    dir: &Path,
+    wallpapers: &[MobileconfWallpaper],
+) -> Result<(), MobileconfError> {
+    fs::create_dir_all(dir).map_err(|source| MobileconfError::Write {
+        path: dir.to_path_buf(),
+        source,
+    })?;
+
+    for wallpaper in wallpapers {
+        let path = payload_file(dir, &wallpaper.PayloadUUID, wallpaper.extension()?);
+        fs::write(&path, base64::decode(&wallpaper.Image)?).map_err(|source| {
+            MobileconfError::Write {
+                path: path.clone(),
+                source,
+            }
+        })?;
+    }
+
+    Result::Ok(())
+}
+
+impl DescribePayload for MobileconfWallpaper {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.wallpaper"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("PayloadUUID", "String"),
+        ("Image", "String"),
+        ("Locations", "Option<i64>"),
+    ];
+}
@@ -0,0 +1,45 @@
+use crate::dict::{as_payload, get_bool_opt, get_data_opt, get_string};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use plist::Value;
+use serde::Serialize;
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfWebClip {
+    pub Label: String,
+    pub URL: String,
+    pub IsRemovable: bool,
+    // base64 png bytes
+    pub Icon: Option<String>,
+}
+
+impl MobileconfWebClip {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.webClip.managed" {
+                return Result::Err(MobileconfError::NotWebClip);
+            }
+        }
+
+        Result::Ok(MobileconfWebClip {
+            Label: get_string(dict, "Label")?,
+            URL: get_string(dict, "URL")?,
+            // clips can be removed unless the profile says otherwise
+            IsRemovable: get_bool_opt(dict, "IsRemovable")?.unwrap_or(true),
+            Icon: get_data_opt(dict, "Icon")?.map(base64::encode),
+        })
+    }
+}
+
+impl DescribePayload for MobileconfWebClip {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.webClip.managed"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("Label", "String"),
+        ("URL", "String"),
+        ("IsRemovable", "bool"),
+        ("Icon", "Option<String>"),
+    ];
+}
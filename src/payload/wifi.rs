@@ -0,0 +1,265 @@
+use crate::dict::{
+    as_payload, get_array, get_bool_opt, get_dict, get_int_opt, get_string, get_string_opt,
+    get_strings_opt,
+};
+use crate::DescribePayload;
+use crate::MobileconfError;
+use crate::Secret;
+use plist::{Dictionary, Value};
+use serde::Serialize;
+
+/// EAP methods as numbered in `AcceptEAPTypes`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum EAPType {
+    Tls,
+    Leap,
+    Ttls,
+    Peap,
+    Fast,
+}
+
+impl EAPType {
+    fn from_code(code: i64) -> Option<Self> {
+        match code {
+            13 => Some(EAPType::Tls),
+            17 => Some(EAPType::Leap),
+            21 => Some(EAPType::Ttls),
+            25 => Some(EAPType::Peap),
+            43 => Some(EAPType::Fast),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn code(self) -> i64 {
+        match self {
+            EAPType::Tls => 13,
+            EAPType::Leap => 17,
+            EAPType::Ttls => 21,
+            EAPType::Peap => 25,
+            EAPType::Fast => 43,
+        }
+    }
+}
+
+/// Proxy to use on the network, from the Proxy* keys next to
+/// EAPClientConfiguration.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WifiProxy {
+    Manual { server: String, port: i64 },
+    // proxy auto-config
+    Auto { pac_url: String },
+}
+
+impl WifiProxy {
+    fn parse(dict: &Dictionary) -> Result<Option<Self>, MobileconfError> {
+        match get_string_opt(dict, "ProxyType")?.as_deref() {
+            Some("Manual") => Result::Ok(Some(WifiProxy::Manual {
+                server: get_string(dict, "ProxyServer")?,
+                port: get_int_opt(dict, "ProxyServerPort")?
+                    .ok_or_else(|| MobileconfError::MissingKey("ProxyServerPort".to_string()))?,
+            })),
+            Some("Auto") => {
+                let pac_url = match get_string_opt(dict, "ProxyPACURL")? {
+                    Some(url) => url,
+                    None => get_string(dict, "ProxyAutoConfigURLString")?,
+                };
+                Result::Ok(Some(WifiProxy::Auto { pac_url }))
+            }
+            _ => Result::Ok(None),
+        }
+    }
+}
+
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfWifi {
+    // pointer to the certificate to use for this connection
+    pub PayloadCertificateAnchorUUID: Vec<String>,
+    pub TLSTrustedServerNames: Vec<String>,
+    // both only optional for EAP-TLS, which authenticates with an identity
+    // certificate instead
+    pub UserName: Option<String>,
+    // anonymous identity sent in the clear before the tunnel is up
+    pub OuterIdentity: Option<String>,
+    pub UserPassword: Option<Secret>,
+    pub SSID: String,
+    // first entry of AcceptEAPTypes that we know about
+    pub EAPType: EAPType,
+    // only present for TTLS
+    pub TTLSInnerAuthentication: Option<String>,
+    // Hotspot 2.0 / Passpoint, next to EAPClientConfiguration
+    pub IsHotspot: Option<bool>,
+    pub DisplayedOperatorName: Option<String>,
+    pub DomainName: Option<String>,
+    pub RoamingConsortiumOIs: Vec<String>,
+    pub proxy: Option<WifiProxy>,
+}
+
+#[allow(non_snake_case)]
+impl MobileconfWifi {
+    #[allow(non_snake_case)]
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.wifi.managed" {
+                return Result::Err(MobileconfError::NotWifi);
+            }
+        }
+
+        // WEP, WPA personal and open networks are MobileconfPersonalWifi's
+        if is_personal(dict) {
+            return Result::Err(MobileconfError::NotWifi);
+        }
+
+        let EAPClientConfiguration = get_dict(dict, "EAPClientConfiguration")?;
+
+        let PayloadCertificateAnchorUUID =
+            get_array(EAPClientConfiguration, "PayloadCertificateAnchorUUID")?
+                .iter()
+                .filter_map(Value::as_string)
+                .map(str::to_string)
+                .collect();
+
+        let TLSTrustedServerNames =
+            get_strings_opt(EAPClientConfiguration, "TLSTrustedServerNames")?.unwrap_or_default();
+
+        let OuterIdentity = match get_string_opt(EAPClientConfiguration, "OuterIdentity")? {
+            Some(identity) => Some(identity),
+            None => get_string_opt(EAPClientConfiguration, "AnonymousUserName")?,
+        };
+
+        let SSID = get_string(dict, "SSID_STR")?;
+
+        let codes: Vec<i64> = get_array(EAPClientConfiguration, "AcceptEAPTypes")?
+            .iter()
+            .filter_map(Value::as_signed_integer)
+            .collect();
+
+        let EAPType = codes
+            .iter()
+            .find_map(|&code| EAPType::from_code(code))
+            .ok_or_else(|| MobileconfError::UnsupportedEAPType(codes.clone()))?;
+
+        let UserName = get_string_opt(EAPClientConfiguration, "UserName")?;
+        let UserPassword =
+            get_string_opt(EAPClientConfiguration, "UserPassword")?.map(Secret::from);
+        if EAPType != EAPType::Tls {
+            if UserName.is_none() {
+                return Result::Err(MobileconfError::MissingKey("UserName".to_string()));
+            }
+            if UserPassword.is_none() {
+                return Result::Err(MobileconfError::MissingKey("UserPassword".to_string()));
+            }
+        }
+
+        let TTLSInnerAuthentication = if EAPType == EAPType::Ttls {
+            Some(get_string(
+                EAPClientConfiguration,
+                "TTLSInnerAuthentication",
+            )?)
+        } else {
+            None
+        };
+
+        let RoamingConsortiumOIs =
+            get_strings_opt(dict, "RoamingConsortiumOIs")?.unwrap_or_default();
+
+        Result::Ok(MobileconfWifi {
+            PayloadCertificateAnchorUUID,
+            TLSTrustedServerNames,
+            UserName,
+            OuterIdentity,
+            UserPassword,
+            SSID,
+            EAPType,
+            TTLSInnerAuthentication,
+            IsHotspot: get_bool_opt(dict, "IsHotspot")?,
+            DisplayedOperatorName: get_string_opt(dict, "DisplayedOperatorName")?,
+            DomainName: get_string_opt(dict, "DomainName")?,
+            RoamingConsortiumOIs,
+            proxy: WifiProxy::parse(dict)?,
+        })
+    }
+}
+
+/// A network secured with a pre shared key, or not at all, rather than EAP.
+/// Open networks can leave out both `EncryptionType` and `Password`, so only
+/// `EAPClientConfiguration` tells the two apart.
+fn is_personal(dict: &Dictionary) -> bool {
+    !dict.contains_key("EAPClientConfiguration")
+}
+
+/// A WEP, WPA personal or open network: a `com.apple.wifi.managed` payload
+/// without `EAPClientConfiguration`.
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfPersonalWifi {
+    pub SSID: String,
+    // WEP, WPA, WPA2, WPA3, Any or None
+    pub EncryptionType: String,
+    pub Password: Option<Secret>,
+    pub HIDDEN_NETWORK: bool,
+    pub AutoJoin: Option<bool>,
+    pub proxy: Option<WifiProxy>,
+}
+
+#[allow(non_snake_case)]
+impl MobileconfPersonalWifi {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = as_payload(v)?;
+
+        if let Result::Ok(typ) = get_string(dict, "PayloadType") {
+            if typ != *"com.apple.wifi.managed" {
+                return Result::Err(MobileconfError::NotPersonalWifi);
+            }
+        }
+
+        if !is_personal(dict) {
+            return Result::Err(MobileconfError::NotPersonalWifi);
+        }
+
+        Result::Ok(MobileconfPersonalWifi {
+            SSID: get_string(dict, "SSID_STR")?,
+            EncryptionType: get_string_opt(dict, "EncryptionType")?
+                .unwrap_or_else(|| "Any".to_string()),
+            Password: get_string_opt(dict, "Password")?.map(Secret::from),
+            HIDDEN_NETWORK: get_bool_opt(dict, "HIDDEN_NETWORK")?.unwrap_or(false),
+            AutoJoin: get_bool_opt(dict, "AutoJoin")?,
+            proxy: WifiProxy::parse(dict)?,
+        })
+    }
+}
+
+impl DescribePayload for MobileconfPersonalWifi {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.wifi.managed"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("SSID", "String"),
+        ("EncryptionType", "String"),
+        ("Password", "Option<Secret>"),
+        ("HIDDEN_NETWORK", "bool"),
+        ("AutoJoin", "Option<bool>"),
+        ("proxy", "Option<WifiProxy>"),
+    ];
+}
+
+impl DescribePayload for MobileconfWifi {
+    const PAYLOAD_TYPES: &'static [&'static str] = &["com.apple.wifi.managed"];
+    const FIELDS: &'static [(&'static str, &'static str)] = &[
+        ("PayloadCertificateAnchorUUID", "Vec<String>"),
+        ("TLSTrustedServerNames", "Vec<String>"),
+        ("UserName", "Option<String>"),
+        ("OuterIdentity", "Option<String>"),
+        ("UserPassword", "Option<Secret>"),
+        ("SSID", "String"),
+        ("EAPType", "EAPType"),
+        ("TTLSInnerAuthentication", "Option<String>"),
+        ("IsHotspot", "Option<bool>"),
+        ("DisplayedOperatorName", "Option<String>"),
+        ("DomainName", "Option<String>"),
+        ("RoamingConsortiumOIs", "Vec<String>"),
+        ("proxy", "Option<WifiProxy>"),
+    ];
+}
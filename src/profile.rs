@@ -0,0 +1,1013 @@
+use crate::dict::{as_payload, get_int_opt, get_string, get_string_opt, get_value};
+use crate::partition_results;
+use crate::CertInfo;
+use crate::MobileconfError;
+use crate::Parsed;
+use crate::PayloadKind;
+use crate::{
+    MobileconfAirPlaySecurity, MobileconfAirPrint, MobileconfAppLock, MobileconfAssociatedDomains,
+    MobileconfCalDAV, MobileconfCardDAV, MobileconfCellular, MobileconfCertTransparency,
+    MobileconfContentFilter, MobileconfDNS, MobileconfDNSProxy, MobileconfEmail,
+    MobileconfExchange, MobileconfExtensibleSSO, MobileconfFont, MobileconfHomeScreen,
+    MobileconfLDAP, MobileconfLockScreen, MobileconfMDM, MobileconfManagedApp,
+    MobileconfNotifications, MobileconfPKCS12, MobileconfPasscode, MobileconfPersonalWifi,
+    MobileconfProxy, MobileconfRelay, MobileconfRestrictions, MobileconfSCEP, MobileconfSSO,
+    MobileconfSubscribedCalendar, MobileconfTLSCert, MobileconfVPN, MobileconfWallpaper,
+    MobileconfWebClip, MobileconfWifi,
+};
+use apply::Apply;
+use flate2::read::GzDecoder;
+use log::{debug, info};
+use openssl::nid::Nid;
+use openssl::pkcs7;
+use openssl::stack;
+use openssl::x509::store;
+use openssl::x509::X509;
+use plist::Value;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::fs;
+use std::io::Cursor;
+use std::io::Read;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::vec;
+
+/// Top-level metadata of the profile itself.
+#[allow(non_snake_case)]
+#[derive(Debug, Serialize)]
+pub struct MobileconfProfile {
+    pub PayloadDisplayName: Option<String>,
+    pub PayloadIdentifier: Option<String>,
+    pub PayloadOrganization: Option<String>,
+    pub PayloadDescription: Option<String>,
+    pub PayloadUUID: Option<String>,
+    pub PayloadVersion: Option<i64>,
+    // "Configuration" for a real profile
+    pub PayloadType: Option<String>,
+}
+
+impl MobileconfProfile {
+    pub fn parse(v: &Value) -> Result<Self, MobileconfError> {
+        let dict = v
+            .as_dictionary()
+            .ok_or_else(|| MobileconfError::wrong_type("profile", "dictionary"))?;
+
+        Result::Ok(MobileconfProfile {
+            PayloadDisplayName: get_string_opt(dict, "PayloadDisplayName")?,
+            PayloadIdentifier: get_string_opt(dict, "PayloadIdentifier")?,
+            PayloadOrganization: get_string_opt(dict, "PayloadOrganization")?,
+            PayloadDescription: get_string_opt(dict, "PayloadDescription")?,
+            PayloadUUID: get_string_opt(dict, "PayloadUUID")?,
+            PayloadVersion: get_int_opt(dict, "PayloadVersion")?,
+            PayloadType: get_string_opt(dict, "PayloadType")?,
+        })
+    }
+}
+
+/// How to treat the PKCS7 signature of a signed profile.
+#[derive(Debug)]
+pub enum Verify {
+    /// Only extract the payload, don't check the signature.
+    No,
+    /// Check the signature against the system CA store.
+    SystemRoots,
+    /// Check the signature against the certificates in a PEM file.
+    CaFile(PathBuf),
+    /// Check the signature against the Apple root CAs built into mobileconf.
+    AppleRoots,
+}
+
+/// Apple's root certificates, as published at
+/// <https://www.apple.com/certificateauthority/>.
+const APPLE_ROOTS: &str = include_str!("apple-roots.pem");
+
+#[derive(Debug)]
+pub struct ParseOptions {
+    pub verify: Verify,
+    // only run these parsers, all of them when empty
+    pub only: Vec<PayloadKind>,
+    // reject cert payloads that don't decode as X509, and profiles whose
+    // PayloadType isn't Configuration, instead of warning
+    pub strict: bool,
+    // refuse profiles with more entries in PayloadContent than this
+    pub max_payloads: usize,
+}
+
+/// Default for `ParseOptions::max_payloads`, far more than any real profile
+/// has.
+pub const DEFAULT_MAX_PAYLOADS: usize = 10_000;
+
+impl ParseOptions {
+    pub fn wants(&self, kind: PayloadKind) -> bool {
+        self.only.is_empty() || self.only.contains(&kind)
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            verify: Verify::No,
+            only: Vec::new(),
+            strict: false,
+            max_payloads: DEFAULT_MAX_PAYLOADS,
+        }
+    }
+}
+
+/// Everything we could extract from a profile. Payloads that failed to parse
+/// are collected in `errors`.
+#[derive(Debug, Serialize)]
+pub struct ParsedProfile {
+    pub profile: MobileconfProfile,
+    // common name of the signer, when the signature was verified
+    pub signer: Option<String>,
+    // number of signatures on the profile, 0 when unsigned
+    pub signers: usize,
+    pub wifis: Vec<MobileconfWifi>,
+    // WEP, WPA personal and open networks
+    pub personal_wifis: Vec<MobileconfPersonalWifi>,
+    pub certs: Vec<MobileconfTLSCert>,
+    pub identities: Vec<MobileconfPKCS12>,
+    pub vpns: Vec<MobileconfVPN>,
+    pub emails: Vec<MobileconfEmail>,
+    pub exchanges: Vec<MobileconfExchange>,
+    pub webclips: Vec<MobileconfWebClip>,
+    pub caldavs: Vec<MobileconfCalDAV>,
+    pub carddavs: Vec<MobileconfCardDAV>,
+    pub ldaps: Vec<MobileconfLDAP>,
+    pub passcodes: Vec<MobileconfPasscode>,
+    pub restrictions: Vec<MobileconfRestrictions>,
+    pub dns: Vec<MobileconfDNS>,
+    pub proxies: Vec<MobileconfProxy>,
+    pub sceps: Vec<MobileconfSCEP>,
+    pub airprints: Vec<MobileconfAirPrint>,
+    pub subscribed_calendars: Vec<MobileconfSubscribedCalendar>,
+    pub cellulars: Vec<MobileconfCellular>,
+    pub extensible_ssos: Vec<MobileconfExtensibleSSO>,
+    pub content_filters: Vec<MobileconfContentFilter>,
+    pub fonts: Vec<MobileconfFont>,
+    pub managed_apps: Vec<MobileconfManagedApp>,
+    pub ssos: Vec<MobileconfSSO>,
+    pub airplays: Vec<MobileconfAirPlaySecurity>,
+    pub notifications: Vec<MobileconfNotifications>,
+    pub lockscreens: Vec<MobileconfLockScreen>,
+    pub dns_proxies: Vec<MobileconfDNSProxy>,
+    pub associated_domains: Vec<MobileconfAssociatedDomains>,
+    pub home_screens: Vec<MobileconfHomeScreen>,
+    pub cert_transparencies: Vec<MobileconfCertTransparency>,
+    pub wallpapers: Vec<MobileconfWallpaper>,
+    pub relays: Vec<MobileconfRelay>,
+    pub mdms: Vec<MobileconfMDM>,
+    pub app_locks: Vec<MobileconfAppLock>,
+    // profiles embedded as payloads of this one
+    pub nested: Vec<NestedProfile>,
+    pub errors: Vec<MobileconfError>,
+    // problems that didn't stop a payload from parsing
+    pub warnings: Vec<String>,
+}
+
+/// A profile found inside a payload of another profile.
+#[derive(Debug, Serialize)]
+pub struct NestedProfile {
+    // index of the payload it was found in
+    pub index: usize,
+    pub profile: ParsedProfile,
+}
+
+impl ParsedProfile {
+    /// How many payloads were found of each category, named like the fields.
+    pub fn counts(&self) -> Vec<(PayloadKind, &'static str, usize)> {
+        vec![
+            (PayloadKind::Wifi, "wifis", self.wifis.len()),
+            (
+                PayloadKind::Wifi,
+                "personal_wifis",
+                self.personal_wifis.len(),
+            ),
+            (PayloadKind::Cert, "certs", self.certs.len()),
+            (PayloadKind::Cert, "identities", self.identities.len()),
+            (PayloadKind::Vpn, "vpns", self.vpns.len()),
+            (PayloadKind::Email, "emails", self.emails.len()),
+            (PayloadKind::Exchange, "exchanges", self.exchanges.len()),
+            (PayloadKind::WebClip, "webclips", self.webclips.len()),
+            (PayloadKind::CalDav, "caldavs", self.caldavs.len()),
+            (PayloadKind::CardDav, "carddavs", self.carddavs.len()),
+            (PayloadKind::Ldap, "ldaps", self.ldaps.len()),
+            (PayloadKind::Passcode, "passcodes", self.passcodes.len()),
+            (
+                PayloadKind::Restrictions,
+                "restrictions",
+                self.restrictions.len(),
+            ),
+            (PayloadKind::Dns, "dns", self.dns.len()),
+            (PayloadKind::Proxy, "proxies", self.proxies.len()),
+            (PayloadKind::Scep, "sceps", self.sceps.len()),
+            (PayloadKind::AirPrint, "airprints", self.airprints.len()),
+            (
+                PayloadKind::SubscribedCalendar,
+                "subscribed_calendars",
+                self.subscribed_calendars.len(),
+            ),
+            (PayloadKind::Cellular, "cellulars", self.cellulars.len()),
+            (
+                PayloadKind::ExtensibleSso,
+                "extensible_ssos",
+                self.extensible_ssos.len(),
+            ),
+            (
+                PayloadKind::ContentFilter,
+                "content_filters",
+                self.content_filters.len(),
+            ),
+            (PayloadKind::Font, "fonts", self.fonts.len()),
+            (
+                PayloadKind::ManagedApp,
+                "managed_apps",
+                self.managed_apps.len(),
+            ),
+            (PayloadKind::Sso, "ssos", self.ssos.len()),
+            (PayloadKind::AirPlay, "airplays", self.airplays.len()),
+            (
+                PayloadKind::Notifications,
+                "notifications",
+                self.notifications.len(),
+            ),
+            (
+                PayloadKind::LockScreen,
+                "lockscreens",
+                self.lockscreens.len(),
+            ),
+            (PayloadKind::DnsProxy, "dns_proxies", self.dns_proxies.len()),
+            (
+                PayloadKind::AssociatedDomains,
+                "associated_domains",
+                self.associated_domains.len(),
+            ),
+            (
+                PayloadKind::HomeScreen,
+                "home_screens",
+                self.home_screens.len(),
+            ),
+            (
+                PayloadKind::CertTransparency,
+                "cert_transparencies",
+                self.cert_transparencies.len(),
+            ),
+            (PayloadKind::Wallpaper, "wallpapers", self.wallpapers.len()),
+            (PayloadKind::Relay, "relays", self.relays.len()),
+            (PayloadKind::Mdm, "mdms", self.mdms.len()),
+            (PayloadKind::AppLock, "app_locks", self.app_locks.len()),
+        ]
+    }
+
+    /// Whether no payload at all could be extracted from the profile.
+    pub fn is_empty(&self) -> bool {
+        self.counts().iter().all(|&(_, _, count)| count == 0)
+            && self.nested.iter().all(|nested| nested.profile.is_empty())
+    }
+}
+
+/// Decompress gzipped input, anything else is passed through.
+fn gunzip(bytes: &[u8]) -> Result<Cow<[u8]>, MobileconfError> {
+    if !bytes.starts_with(b"\x1f\x8b") {
+        return Result::Ok(Cow::Borrowed(bytes));
+    }
+
+    let mut decompressed = Vec::new();
+    GzDecoder::new(bytes)
+        .read_to_end(&mut decompressed)
+        .map_err(MobileconfError::Gzip)?;
+    debug!("gunzipped {} bytes", decompressed.len());
+    Result::Ok(Cow::Owned(decompressed))
+}
+
+/// Skip a byte order mark and leading whitespace.
+fn trim_start(bytes: &[u8]) -> &[u8] {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    let start = bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+/// Unsigned profiles are plain XML or binary plists rather than PKCS7.
+fn is_plist(bytes: &[u8]) -> bool {
+    let bytes = trim_start(bytes);
+    bytes.starts_with(b"<?xml") || bytes.starts_with(b"<plist") || bytes.starts_with(b"bplist")
+}
+
+fn is_pem_pkcs7(bytes: &[u8]) -> bool {
+    trim_start(bytes).starts_with(b"-----BEGIN PKCS7-----")
+}
+
+/// The shapes a profile can come in, after decompression.
+enum Loaded<'a> {
+    // an unsigned plist, XML or binary
+    Plist(Cow<'a, [u8]>),
+    // a signed profile, DER or PEM armored
+    Pkcs7(pkcs7::Pkcs7),
+}
+
+fn load_pkcs7_or_plist(bytes: &[u8]) -> Result<Loaded, MobileconfError> {
+    let bytes = gunzip(bytes)?;
+    if is_plist(&bytes) {
+        info!("input is an unsigned plist");
+        Result::Ok(Loaded::Plist(bytes))
+    } else if is_pem_pkcs7(&bytes) {
+        info!("input is a PEM PKCS7 envelope");
+        Result::Ok(Loaded::Pkcs7(pkcs7::Pkcs7::from_pem(&bytes)?))
+    } else {
+        info!("input is a DER PKCS7 envelope");
+        Result::Ok(Loaded::Pkcs7(pkcs7::Pkcs7::from_der(&bytes)?))
+    }
+}
+
+fn signer_common_name(signers: &stack::StackRef<X509>) -> Option<String> {
+    signers
+        .iter()
+        .next()
+        .and_then(|cert| cert.subject_name().entries_by_nid(Nid::COMMONNAME).next())
+        .and_then(|entry| entry.data().as_utf8().ok())
+        .map(|cn| cn.to_string())
+}
+
+/// What we learned from the PKCS7 envelope around the plist.
+#[derive(Default)]
+struct Envelope {
+    // set when the signature was verified
+    signer: Option<String>,
+    signers: usize,
+}
+
+/// Returns the payload and what the envelope says about who signed it.
+fn extract_pkcs7_payload(
+    p7: &pkcs7::Pkcs7Ref,
+    verify: &Verify,
+) -> Result<(Vec<u8>, Envelope), MobileconfError> {
+    let stack = stack::Stack::new()?;
+
+    let mut store = store::X509StoreBuilder::new()?;
+
+    let mut flags = pkcs7::Pkcs7Flags::empty();
+
+    match verify {
+        // we just want to get the payload, these inputs gets us that.
+        Verify::No => flags.insert(pkcs7::Pkcs7Flags::NOVERIFY),
+        Verify::SystemRoots => store.set_default_paths()?,
+        Verify::CaFile(path) => {
+            let pem = fs::read(path).map_err(|source| MobileconfError::Read {
+                path: path.clone(),
+                source,
+            })?;
+            for cert in X509::stack_from_pem(&pem)? {
+                store.add_cert(cert)?;
+            }
+        }
+        Verify::AppleRoots => {
+            let roots = X509::stack_from_pem(APPLE_ROOTS.as_bytes())?;
+            if roots.is_empty() {
+                return Result::Err(MobileconfError::NoAppleRoots);
+            }
+            for cert in roots {
+                store.add_cert(cert)?;
+            }
+        }
+    }
+
+    let store = store.build();
+
+    let mut xml: vec::Vec<u8> = vec::Vec::new();
+
+    p7.verify(&stack, &store, None, Some(&mut xml), flags)
+        .map_err(MobileconfError::Signature)?;
+
+    // co-signed profiles carry more than one signer, count them all rather
+    // than only looking at the first
+    let certs = stack::Stack::new()?;
+    let signers = p7.signers(&certs, pkcs7::Pkcs7Flags::empty())?;
+
+    let signer = match verify {
+        Verify::No => None,
+        _ => signer_common_name(&signers),
+    };
+
+    Result::Ok((
+        xml,
+        Envelope {
+            signer,
+            signers: signers.len(),
+        },
+    ))
+}
+
+/// Details of the certificates that signed the profile, `None` if it is
+/// unsigned. The signature is not verified.
+pub fn signer_info(bytes: &[u8]) -> Result<Option<Vec<CertInfo>>, MobileconfError> {
+    let p7 = match load_pkcs7_or_plist(bytes)? {
+        Loaded::Plist(_) => return Result::Ok(None),
+        Loaded::Pkcs7(p7) => p7,
+    };
+
+    let certs = stack::Stack::new()?;
+    let signers = p7.signers(&certs, pkcs7::Pkcs7Flags::empty())?;
+
+    let infos = signers
+        .iter()
+        .map(CertInfo::from_x509)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Result::Ok(Some(infos))
+}
+
+/// Parse a signed or unsigned `.mobileconfig` file.
+pub fn parse_profile(bytes: &[u8]) -> Result<ParsedProfile, MobileconfError> {
+    parse_profile_with(bytes, &ParseOptions::default())
+}
+
+/// Same as `parse_profile`: `ParsedProfile::try_from(bytes)?` takes DER or
+/// PEM signed profiles as well as bare XML and binary plists.
+impl TryFrom<&[u8]> for ParsedProfile {
+    type Error = MobileconfError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        parse_profile(bytes)
+    }
+}
+
+/// For profiles already read as text, XML or PEM.
+impl FromStr for ParsedProfile {
+    type Err = MobileconfError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_profile(s.as_bytes())
+    }
+}
+
+/// The raw plist inside the (possibly signed) profile, and its envelope.
+fn unwrap_profile(bytes: &[u8], verify: &Verify) -> Result<(Vec<u8>, Envelope), MobileconfError> {
+    match load_pkcs7_or_plist(bytes)? {
+        Loaded::Plist(plist) => {
+            if let Verify::No = verify {
+                Result::Ok((plist.into_owned(), Envelope::default()))
+            } else {
+                Result::Err(MobileconfError::Unsigned)
+            }
+        }
+        Loaded::Pkcs7(p7) => extract_pkcs7_payload(&p7, verify),
+    }
+}
+
+/// The plist inside the profile exactly as it was embedded, which may be XML
+/// or binary.
+pub fn extract_plist(bytes: &[u8], options: &ParseOptions) -> Result<Vec<u8>, MobileconfError> {
+    unwrap_profile(bytes, &options.verify).map(|(plist, _)| plist)
+}
+
+pub(crate) fn load_plist(
+    bytes: &[u8],
+    verify: &Verify,
+) -> Result<(Value, Envelope), MobileconfError> {
+    let (xml, envelope) = unwrap_profile(bytes, verify)?;
+
+    // from_reader sniffs the `bplist00` magic, so binary plists work both
+    // bare and inside the PKCS7 envelope
+    let plist = Value::from_reader(Cursor::new(xml))?;
+    Result::Ok((plist, envelope))
+}
+
+pub(crate) fn payload_contents(plist: &Value) -> Result<&[Value], MobileconfError> {
+    let dict = plist
+        .as_dictionary()
+        .ok_or_else(|| MobileconfError::wrong_type("profile", "dictionary"))?;
+
+    // single payload profiles sometimes skip the array
+    match get_value(dict, "PayloadContent")? {
+        Value::Array(contents) => Result::Ok(contents),
+        single @ Value::Dictionary(_) => Result::Ok(std::slice::from_ref(single)),
+        _ => Result::Err(MobileconfError::wrong_type("PayloadContent", "array")),
+    }
+}
+
+/// Like `payload_contents`, but errors out before anything is parsed if there
+/// are more payloads than `options.max_payloads`.
+pub(crate) fn limited_payload_contents<'a>(
+    plist: &'a Value,
+    options: &ParseOptions,
+) -> Result<&'a [Value], MobileconfError> {
+    let contents = payload_contents(plist)?;
+    if contents.len() > options.max_payloads {
+        return Result::Err(MobileconfError::TooManyPayloads {
+            count: contents.len(),
+            max: options.max_payloads,
+        });
+    }
+    Result::Ok(contents)
+}
+
+/// Count the payloads of each `PayloadType` in the profile, including types
+/// there is no parser for. Payloads without a type are counted as `(none)`.
+pub fn payload_types(
+    bytes: &[u8],
+    options: &ParseOptions,
+) -> Result<BTreeMap<String, usize>, MobileconfError> {
+    let (plist, _) = load_plist(bytes, &options.verify)?;
+
+    let mut types = BTreeMap::new();
+    for payload in limited_payload_contents(&plist, options)? {
+        let typ = payload
+            .as_dictionary()
+            .and_then(|dict| get_string_opt(dict, "PayloadType").ok().flatten())
+            .unwrap_or_else(|| "(none)".to_string());
+        *types.entry(typ).or_insert(0) += 1;
+    }
+
+    Result::Ok(types)
+}
+
+/// Only the top-level metadata of the profile, without parsing any payload.
+pub fn profile_metadata(
+    bytes: &[u8],
+    options: &ParseOptions,
+) -> Result<MobileconfProfile, MobileconfError> {
+    let (plist, _) = load_plist(bytes, &options.verify)?;
+    MobileconfProfile::parse(&plist)
+}
+
+/// The `index`th entry of PayloadContent as it is in the plist, for payloads
+/// we have no parser for.
+pub fn raw_payload(
+    bytes: &[u8],
+    options: &ParseOptions,
+    index: usize,
+) -> Result<Value, MobileconfError> {
+    let (plist, _) = load_plist(bytes, &options.verify)?;
+    let contents = payload_contents(&plist)?;
+
+    contents
+        .get(index)
+        .cloned()
+        .ok_or(MobileconfError::NoSuchPayload {
+            index,
+            count: contents.len(),
+        })
+}
+
+/// Say which payload an error came from.
+fn payload_error(index: usize, payload: &Value, err: MobileconfError) -> MobileconfError {
+    MobileconfError::Payload {
+        index,
+        uuid: payload
+            .as_dictionary()
+            .and_then(|dict| dict.get("PayloadUUID"))
+            .and_then(Value::as_string)
+            .map(str::to_string),
+        source: Box::new(err),
+    }
+}
+
+/// The payloads grouped by the parser their PayloadType routes them to,
+/// along with their index in PayloadContent.
+struct Routes<'a>(HashMap<PayloadKind, Vec<(usize, &'a Value)>>);
+
+impl<'a> Routes<'a> {
+    /// Read the PayloadType of each payload once. Payloads without one are
+    /// returned as errors, ones we have no parser for are left out.
+    fn new(options: &ParseOptions, contents: &'a [Value]) -> (Self, Vec<MobileconfError>) {
+        let mut routes = HashMap::new();
+        let mut untyped = Vec::new();
+
+        for (index, payload) in contents.iter().enumerate() {
+            let typ = match as_payload(payload).and_then(|dict| get_string(dict, "PayloadType")) {
+                Result::Ok(typ) => typ,
+                Result::Err(err) => {
+                    untyped.push(payload_error(index, payload, err));
+                    continue;
+                }
+            };
+
+            if let Some(kind) = PayloadKind::of(&typ).filter(|&kind| options.wants(kind)) {
+                routes
+                    .entry(kind)
+                    .or_insert_with(Vec::new)
+                    .push((index, payload));
+            }
+        }
+
+        (Routes(routes), untyped)
+    }
+
+    fn get(&self, kind: PayloadKind) -> &[(usize, &'a Value)] {
+        self.0.get(&kind).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+/// Run `parse` over the payloads, saying which payload it was when it fails.
+/// Payloads it turns down for being of another type are left out.
+fn parse_each<'a, T>(
+    payloads: &'a [(usize, &'a Value)],
+    parse: fn(&Value) -> Result<T, MobileconfError>,
+) -> impl Iterator<Item = Result<T, MobileconfError>> + 'a {
+    payloads.iter().filter_map(move |&(index, payload)| {
+        Parsed::from(parse(payload))
+            .matched()
+            .map(|result| result.map_err(|err| payload_error(index, payload, err)))
+    })
+}
+
+/// UUIDs shared by more than one payload, which breaks references between
+/// payloads such as wifi certificate anchors.
+fn duplicate_uuids(contents: &[Value]) -> BTreeMap<String, usize> {
+    let mut seen = BTreeMap::new();
+    for payload in contents {
+        if let Some(uuid) = payload
+            .as_dictionary()
+            .and_then(|dict| dict.get("PayloadUUID"))
+            .and_then(Value::as_string)
+        {
+            *seen.entry(uuid.to_string()).or_insert(0) += 1;
+        }
+    }
+
+    seen.retain(|_, count| *count > 1);
+    seen
+}
+
+pub fn parse_profile_with(
+    bytes: &[u8],
+    options: &ParseOptions,
+) -> Result<ParsedProfile, MobileconfError> {
+    let (plist, envelope) = load_plist(bytes, &options.verify)?;
+    parse_plist(&plist, envelope, options, 0)
+}
+
+/// How deep profiles may be nested inside each other before we give up.
+const MAX_NESTING: usize = 4;
+
+/// The profile embedded in a payload, if it is one: either a
+/// `com.apple.configurationprofile` payload or one whose PayloadContent is an
+/// unsigned profile.
+fn nested_plist(payload: &Value) -> Option<Result<Value, MobileconfError>> {
+    let dict = payload.as_dictionary()?;
+    let is_profile = get_string_opt(dict, "PayloadType")
+        .ok()
+        .flatten()
+        .as_deref()
+        == Some("com.apple.configurationprofile");
+
+    match dict.get("PayloadContent")? {
+        // the outer signature covers the inner profile, so its own is not
+        // verified
+        Value::Data(bytes) if is_profile || is_plist(bytes) => {
+            Some(load_plist(bytes, &Verify::No).map(|(plist, _)| plist))
+        }
+        content @ Value::Dictionary(_) if is_profile => Some(Result::Ok(content.clone())),
+        _ if is_profile => Some(Result::Err(MobileconfError::wrong_type(
+            "PayloadContent",
+            "data or dictionary",
+        ))),
+        _ => None,
+    }
+}
+
+fn parse_plist(
+    plist: &Value,
+    envelope: Envelope,
+    options: &ParseOptions,
+    depth: usize,
+) -> Result<ParsedProfile, MobileconfError> {
+    let profile = MobileconfProfile::parse(plist)?;
+
+    // a single payload saved on its own looks a lot like a profile
+    let mut not_configuration = match profile.PayloadType.as_deref() {
+        Some("Configuration") => None,
+        typ => Some(MobileconfError::NotConfiguration(typ.map(str::to_string))),
+    };
+    if options.strict {
+        if let Some(err) = not_configuration.take() {
+            return Result::Err(err);
+        }
+    }
+
+    let contents = limited_payload_contents(plist, options)?;
+    info!("{} payloads at depth {}", contents.len(), depth);
+    let (routes, untyped_errs) = Routes::new(options, contents);
+
+    let mut nested = Vec::new();
+    let mut nested_errs = Vec::new();
+    for (index, payload) in contents.iter().enumerate() {
+        let inner = match nested_plist(payload) {
+            Some(_) if depth >= MAX_NESTING => {
+                Result::Err(MobileconfError::TooDeeplyNested(MAX_NESTING))
+            }
+            Some(inner) => {
+                inner.and_then(|inner| parse_plist(&inner, Envelope::default(), options, depth + 1))
+            }
+            None => continue,
+        };
+
+        match inner {
+            Result::Ok(profile) => nested.push(NestedProfile { index, profile }),
+            Result::Err(err) => nested_errs.push(payload_error(index, payload, err)),
+        }
+    }
+
+    let (wifis, wifi_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::Wifi), MobileconfWifi::parse).apply(partition_results);
+
+    let (personal_wifis, personal_wifi_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::Wifi), MobileconfPersonalWifi::parse)
+            .apply(partition_results);
+
+    let (certs, mut cert_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::Cert), MobileconfTLSCert::parse)
+            .apply(partition_results);
+
+    let (identities, identity_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::Cert), MobileconfPKCS12::parse).apply(partition_results);
+
+    let (vpns, vpn_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::Vpn), MobileconfVPN::parse)
+            .filter_map(Result::transpose)
+            .apply(partition_results);
+
+    let (emails, email_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::Email), MobileconfEmail::parse).apply(partition_results);
+
+    let (exchanges, exchange_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::Exchange), MobileconfExchange::parse)
+            .apply(partition_results);
+
+    let (webclips, webclip_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::WebClip), MobileconfWebClip::parse)
+            .apply(partition_results);
+
+    let (caldavs, caldav_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::CalDav), MobileconfCalDAV::parse)
+            .apply(partition_results);
+
+    let (carddavs, carddav_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::CardDav), MobileconfCardDAV::parse)
+            .apply(partition_results);
+
+    let (ldaps, ldap_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::Ldap), MobileconfLDAP::parse).apply(partition_results);
+
+    let (passcodes, passcode_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::Passcode), MobileconfPasscode::parse)
+            .apply(partition_results);
+
+    let (restrictions, restriction_errs): (Vec<_>, Vec<_>) = parse_each(
+        routes.get(PayloadKind::Restrictions),
+        MobileconfRestrictions::parse,
+    )
+    .apply(partition_results);
+
+    let (dns, dns_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::Dns), MobileconfDNS::parse).apply(partition_results);
+
+    let (proxies, proxy_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::Proxy), MobileconfProxy::parse).apply(partition_results);
+
+    let (sceps, scep_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::Scep), MobileconfSCEP::parse).apply(partition_results);
+
+    let (airprints, airprint_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::AirPrint), MobileconfAirPrint::parse)
+            .apply(partition_results);
+
+    let (subscribed_calendars, subscribed_calendar_errs): (Vec<_>, Vec<_>) = parse_each(
+        routes.get(PayloadKind::SubscribedCalendar),
+        MobileconfSubscribedCalendar::parse,
+    )
+    .apply(partition_results);
+
+    let (cellulars, cellular_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::Cellular), MobileconfCellular::parse)
+            .apply(partition_results);
+
+    let (extensible_ssos, extensible_sso_errs): (Vec<_>, Vec<_>) = parse_each(
+        routes.get(PayloadKind::ExtensibleSso),
+        MobileconfExtensibleSSO::parse,
+    )
+    .apply(partition_results);
+
+    let (content_filters, content_filter_errs): (Vec<_>, Vec<_>) = parse_each(
+        routes.get(PayloadKind::ContentFilter),
+        MobileconfContentFilter::parse,
+    )
+    .apply(partition_results);
+
+    let (fonts, font_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::Font), MobileconfFont::parse).apply(partition_results);
+
+    let (managed_apps, managed_app_errs): (Vec<_>, Vec<_>) = parse_each(
+        routes.get(PayloadKind::ManagedApp),
+        MobileconfManagedApp::parse,
+    )
+    .apply(partition_results);
+
+    let (ssos, sso_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::Sso), MobileconfSSO::parse).apply(partition_results);
+
+    let (airplays, airplay_errs): (Vec<_>, Vec<_>) = parse_each(
+        routes.get(PayloadKind::AirPlay),
+        MobileconfAirPlaySecurity::parse,
+    )
+    .apply(partition_results);
+
+    let (notifications, notification_errs): (Vec<_>, Vec<_>) = parse_each(
+        routes.get(PayloadKind::Notifications),
+        MobileconfNotifications::parse,
+    )
+    .apply(partition_results);
+
+    let (lockscreens, lockscreen_errs): (Vec<_>, Vec<_>) = parse_each(
+        routes.get(PayloadKind::LockScreen),
+        MobileconfLockScreen::parse,
+    )
+    .apply(partition_results);
+
+    let (dns_proxies, dns_proxy_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::DnsProxy), MobileconfDNSProxy::parse)
+            .apply(partition_results);
+
+    let (associated_domains, associated_domain_errs): (Vec<_>, Vec<_>) = parse_each(
+        routes.get(PayloadKind::AssociatedDomains),
+        MobileconfAssociatedDomains::parse,
+    )
+    .apply(partition_results);
+
+    let (home_screens, home_screen_errs): (Vec<_>, Vec<_>) = parse_each(
+        routes.get(PayloadKind::HomeScreen),
+        MobileconfHomeScreen::parse,
+    )
+    .apply(partition_results);
+
+    let (cert_transparencies, cert_transparency_errs): (Vec<_>, Vec<_>) = parse_each(
+        routes.get(PayloadKind::CertTransparency),
+        MobileconfCertTransparency::parse,
+    )
+    .apply(partition_results);
+
+    let (wallpapers, wallpaper_errs): (Vec<_>, Vec<_>) = parse_each(
+        routes.get(PayloadKind::Wallpaper),
+        MobileconfWallpaper::parse,
+    )
+    .apply(partition_results);
+
+    let (relays, relay_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::Relay), MobileconfRelay::parse).apply(partition_results);
+
+    let (mdms, mdm_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::Mdm), MobileconfMDM::parse).apply(partition_results);
+
+    let (app_locks, app_lock_errs): (Vec<_>, Vec<_>) =
+        parse_each(routes.get(PayloadKind::AppLock), MobileconfAppLock::parse)
+            .apply(partition_results);
+
+    let mut warnings = duplicate_uuids(contents)
+        .into_iter()
+        .map(|(uuid, count)| format!("PayloadUUID {} is used by {} payloads", uuid, count))
+        .collect::<Vec<_>>();
+
+    if let Some(err) = not_configuration {
+        warnings.push(err.to_string());
+    }
+
+    let mut checked_certs = Vec::with_capacity(certs.len());
+    for cert in certs {
+        match cert.to_x509() {
+            Result::Ok(_) => checked_certs.push(cert),
+            Result::Err(err) => {
+                let err = MobileconfError::InvalidCertificate {
+                    uuid: cert.PayloadUUID.clone(),
+                    source: Box::new(err),
+                };
+                if options.strict {
+                    cert_errs.push(err);
+                } else {
+                    warnings.push(err.to_string());
+                    checked_certs.push(cert);
+                }
+            }
+        }
+    }
+    let certs = checked_certs;
+
+    for airprint in &airprints {
+        if airprint.skipped > 0 {
+            warnings.push(format!(
+                "skipped {} AirPrint printer(s) without an IPAddress",
+                airprint.skipped
+            ));
+        }
+    }
+
+    for cellular in &cellulars {
+        let sensitive = cellular.carrier_sensitive();
+        if !sensitive.is_empty() {
+            warnings.push(format!(
+                "cellular payload carries eSIM activation values: {}",
+                sensitive.join(", ")
+            ));
+        }
+    }
+
+    let errors = untyped_errs
+        .into_iter()
+        .chain(nested_errs)
+        .chain(wifi_errs)
+        .chain(personal_wifi_errs)
+        .chain(cert_errs)
+        .chain(identity_errs)
+        .chain(vpn_errs)
+        .chain(email_errs)
+        .chain(exchange_errs)
+        .chain(webclip_errs)
+        .chain(caldav_errs)
+        .chain(carddav_errs)
+        .chain(ldap_errs)
+        .chain(passcode_errs)
+        .chain(restriction_errs)
+        .chain(dns_errs)
+        .chain(proxy_errs)
+        .chain(scep_errs)
+        .chain(airprint_errs)
+        .chain(subscribed_calendar_errs)
+        .chain(cellular_errs)
+        .chain(extensible_sso_errs)
+        .chain(content_filter_errs)
+        .chain(font_errs)
+        .chain(managed_app_errs)
+        .chain(sso_errs)
+        .chain(airplay_errs)
+        .chain(notification_errs)
+        .chain(lockscreen_errs)
+        .chain(dns_proxy_errs)
+        .chain(associated_domain_errs)
+        .chain(home_screen_errs)
+        .chain(cert_transparency_errs)
+        .chain(wallpaper_errs)
+        .chain(relay_errs)
+        .chain(mdm_errs)
+        .chain(app_lock_errs)
+        .collect();
+
+    let parsed = ParsedProfile {
+        profile,
+        signer: envelope.signer,
+        signers: envelope.signers,
+        wifis,
+        personal_wifis,
+        certs,
+        identities,
+        vpns,
+        emails,
+        exchanges,
+        webclips,
+        caldavs,
+        carddavs,
+        ldaps,
+        passcodes,
+        restrictions,
+        dns,
+        proxies,
+        sceps,
+        airprints,
+        subscribed_calendars,
+        cellulars,
+        extensible_ssos,
+        content_filters,
+        fonts,
+        managed_apps,
+        ssos,
+        airplays,
+        notifications,
+        lockscreens,
+        dns_proxies,
+        associated_domains,
+        home_screens,
+        cert_transparencies,
+        wallpapers,
+        relays,
+        mdms,
+        app_locks,
+        nested,
+        errors,
+        warnings,
+    };
+
+    for (_, name, count) in parsed.counts() {
+        if count > 0 {
+            debug!("parsed {} {}", count, name);
+        }
+    }
+    debug!("{} payloads failed to parse", parsed.errors.len());
+
+    Result::Ok(parsed)
+}
@@ -0,0 +1,59 @@
+use serde::Serialize;
+use serde::Serializer;
+use std::fmt;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+static SHOW_SECRETS: AtomicBool = AtomicBool::new(false);
+
+/// Print secrets in all output instead of redacting them.
+pub fn set_show_secrets(show: bool) {
+    SHOW_SECRETS.store(show, Ordering::Relaxed);
+}
+
+pub(crate) fn show_secrets() -> bool {
+    SHOW_SECRETS.load(Ordering::Relaxed)
+}
+
+/// A string that is redacted in all output unless `set_show_secrets` is
+/// turned on. The real value is always available through `expose`.
+#[derive(Clone, PartialEq)]
+pub struct Secret(String);
+
+impl Secret {
+    pub fn expose(&self) -> &str {
+        &self.0
+    }
+
+    fn shown(&self) -> &str {
+        if show_secrets() {
+            &self.0
+        } else {
+            "<redacted>"
+        }
+    }
+}
+
+impl From<String> for Secret {
+    fn from(s: String) -> Self {
+        Secret(s)
+    }
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.shown(), f)
+    }
+}
+
+impl fmt::Display for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.shown())
+    }
+}
+
+impl Serialize for Secret {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.shown())
+    }
+}
@@ -0,0 +1,28 @@
+use openssl::nid::Nid;
+use openssl::x509::X509;
+
+const APPLE_ROOTS: &str = include_str!("../src/apple-roots.pem");
+
+/// `--apple-roots` trusts exactly Apple Root CA, G2 and G3.
+#[test]
+fn bundled_apple_roots() {
+    let roots = X509::stack_from_pem(APPLE_ROOTS.as_bytes()).unwrap();
+
+    let mut names: Vec<String> = roots
+        .iter()
+        .map(|root| {
+            root.subject_name()
+                .entries_by_nid(Nid::COMMONNAME)
+                .next()
+                .and_then(|name| name.data().as_utf8().ok())
+                .map(|name| name.to_string())
+                .unwrap_or_default()
+        })
+        .collect();
+    names.sort();
+
+    assert_eq!(
+        names,
+        vec!["Apple Root CA", "Apple Root CA - G2", "Apple Root CA - G3"]
+    );
+}
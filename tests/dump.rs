@@ -0,0 +1,81 @@
+use mobileconf::{
+    dump_certs, dump_fonts, dump_wallpapers, CertFormat, MobileconfFont, MobileconfTLSCert,
+    MobileconfWallpaper,
+};
+use std::fs;
+use std::path::PathBuf;
+
+/// A fresh, empty directory under the system temp dir.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join(format!("mobileconf-{}", std::process::id()))
+        .join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Every file written, relative to `dir`, sorted.
+fn written(dir: &PathBuf) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+/// A `PayloadUUID` trying to escape `--dump-certs DIR` ends up as a file
+/// inside it.
+#[test]
+fn dump_certs_with_hostile_uuids() {
+    let root = scratch_dir("hostile-certs");
+    let dir = root.join("certs");
+
+    let certs: Vec<MobileconfTLSCert> = ["../escaped", "/tmp/absolute", ".."]
+        .iter()
+        .map(|uuid| MobileconfTLSCert {
+            PayloadUUID: uuid.to_string(),
+            PayloadContent: base64::encode(b"not really a cert"),
+        })
+        .collect();
+    dump_certs(&dir, &certs, CertFormat::Der).unwrap();
+
+    assert_eq!(
+        written(&dir),
+        vec![".._escaped.der", "__.der", "_tmp_absolute.der"]
+    );
+    assert_eq!(written(&root), vec!["certs"]);
+}
+
+#[test]
+fn dump_fonts_with_hostile_uuid() {
+    let root = scratch_dir("hostile-fonts");
+    let dir = root.join("fonts");
+
+    let fonts = vec![MobileconfFont {
+        PayloadUUID: "../../escaped".to_string(),
+        Name: None,
+        Font: base64::encode(b"OTTO not really a font"),
+    }];
+    dump_fonts(&dir, &fonts).unwrap();
+
+    assert_eq!(written(&dir), vec![".._.._escaped.otf"]);
+    assert_eq!(written(&root), vec!["fonts"]);
+}
+
+#[test]
+fn dump_wallpapers_with_hostile_uuid() {
+    let root = scratch_dir("hostile-wallpapers");
+    let dir = root.join("wallpapers");
+
+    let wallpapers = vec![MobileconfWallpaper {
+        PayloadUUID: "/tmp/absolute".to_string(),
+        Image: base64::encode(b"\x89PNG\r\n\x1a\n not really an image"),
+        Locations: None,
+    }];
+    dump_wallpapers(&dir, &wallpapers).unwrap();
+
+    assert_eq!(written(&dir), vec!["_tmp_absolute.png"]);
+    assert_eq!(written(&root), vec!["wallpapers"]);
+}
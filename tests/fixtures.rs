@@ -0,0 +1,104 @@
+use mobileconf::{diff_profiles, parse_profile, EAPType, ParseOptions, ParsedProfile};
+use std::fs;
+use std::path::Path;
+
+fn fixture(name: &str) -> Vec<u8> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("fixtures")
+        .join(name);
+    fs::read(&path).unwrap_or_else(|err| panic!("{}: {}", path.display(), err))
+}
+
+/// Every wifi fixture holds the same single TTLS network.
+fn assert_example_wifi(parsed: &ParsedProfile) {
+    assert!(parsed.errors.is_empty(), "errors: {:?}", parsed.errors);
+    assert_eq!(parsed.wifis.len(), 1);
+
+    let wifi = &parsed.wifis[0];
+    assert_eq!(wifi.SSID, "ExampleCorp");
+    assert_eq!(wifi.EAPType, EAPType::Ttls);
+    assert_eq!(wifi.UserName.as_deref(), Some("alice"));
+}
+
+#[test]
+fn unsigned_xml_profile() {
+    let parsed = parse_profile(&fixture("unsigned-wifi.mobileconfig")).unwrap();
+    assert_example_wifi(&parsed);
+    assert_eq!(parsed.signers, 0);
+}
+
+#[test]
+fn unsigned_binary_profile() {
+    let parsed = parse_profile(&fixture("binary-wifi.mobileconfig")).unwrap();
+    assert_example_wifi(&parsed);
+    assert_eq!(parsed.signers, 0);
+}
+
+/// A bplist00 document inside a DER PKCS7 envelope, signed by a throwaway
+/// self-signed certificate.
+#[test]
+fn signed_binary_profile() {
+    let parsed = parse_profile(&fixture("signed-binary-wifi.mobileconfig")).unwrap();
+    assert_example_wifi(&parsed);
+    assert_eq!(parsed.signers, 1);
+    assert_eq!(parsed.signer.as_deref(), Some("mobileconf test signer"));
+}
+
+/// A network with neither `EncryptionType` nor `Password` is open, not EAP.
+#[test]
+fn open_wifi_profile() {
+    let parsed = parse_profile(&fixture("open-wifi.mobileconfig")).unwrap();
+    assert!(parsed.errors.is_empty(), "errors: {:?}", parsed.errors);
+    assert!(parsed.wifis.is_empty());
+    assert_eq!(parsed.personal_wifis.len(), 1);
+
+    let wifi = &parsed.personal_wifis[0];
+    assert_eq!(wifi.SSID, "ExampleGuest");
+    assert!(wifi.Password.is_none());
+}
+
+/// Per-app VPN payloads carry VPN settings rather than a bundle identifier, so
+/// they are read as VPNs and not as managed app configuration.
+#[test]
+fn per_app_vpn_profile() {
+    let parsed = parse_profile(&fixture("per-app-vpn.mobileconfig")).unwrap();
+    assert!(parsed.errors.is_empty(), "errors: {:?}", parsed.errors);
+    assert!(parsed.managed_apps.is_empty());
+    assert_eq!(parsed.vpns.len(), 1);
+
+    let vpn = &parsed.vpns[0];
+    assert_eq!(vpn.RemoteAddress, "vpn.example.com");
+    assert_eq!(
+        vpn.VPNUUID.as_deref(),
+        Some("8293A4B5-C6D7-4E8F-9A0B-1C2D3E4F5A6B")
+    );
+    assert_eq!(vpn.SafariDomains, vec!["intranet.example.com"]);
+
+    // disabled rules are still reported, for auditing
+    assert!(!vpn.OnDemandEnabled);
+    assert_eq!(vpn.OnDemandRules.len(), 1);
+    assert_eq!(vpn.OnDemandRules[0].Action, "Connect");
+}
+
+/// Changes to the wifi fields don't hide changes to the payload's other keys.
+#[test]
+fn diff_reports_wifi_and_other_keys() {
+    let old = fixture("unsigned-wifi.mobileconfig");
+    let new = String::from_utf8(old.clone())
+        .unwrap()
+        .replace("<string>alice</string>", "<string>bob</string>")
+        .replace(
+            "<string>Example WiFi</string>",
+            "<string>Staff WiFi</string>",
+        );
+
+    let diff = diff_profiles(&old, new.as_bytes(), &ParseOptions::default()).unwrap();
+    assert_eq!(diff.changed.len(), 1);
+
+    let fields: Vec<&str> = diff.changed[0]
+        .changes
+        .iter()
+        .map(|change| change.field.as_str())
+        .collect();
+    assert_eq!(fields, vec!["UserName", "PayloadDisplayName"]);
+}